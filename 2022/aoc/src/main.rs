@@ -0,0 +1,46 @@
+use anyhow::{anyhow, Error};
+use clap::Parser;
+
+use common::{run_entry, verify_all, Registry};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct AocArgs {
+    /// Day to run
+    #[arg(long, required_unless_present = "verify_all")]
+    day: Option<u8>,
+    /// Check every day against its known answers
+    #[arg(long, conflicts_with = "day")]
+    verify_all: bool,
+    #[command(flatten)]
+    run: common::Args,
+}
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register::<day1::Day1>();
+    registry.register::<day2::Day2>();
+    registry.register::<day3::Day3>();
+    registry.register::<day4::Day4>();
+    registry.register::<day5::Day5>();
+    registry.register::<day6::Day6>();
+    registry.register::<day10::Day10>();
+    registry
+}
+
+fn main() -> Result<(), Error> {
+    let args = AocArgs::parse();
+    let registry = registry();
+    if args.verify_all {
+        return if verify_all(&registry)? {
+            Ok(())
+        } else {
+            Err(anyhow!("Answers do not match answers.toml"))
+        };
+    }
+    let day = args.day.ok_or_else(|| anyhow!("No day given"))?;
+    let entry = registry
+        .get(day)
+        .ok_or_else(|| anyhow!("No solver for day {}", day))?;
+    run_entry(entry, &args.run)
+}
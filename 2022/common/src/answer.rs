@@ -0,0 +1,124 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use crate::ocr::read_letters;
+
+/// A puzzle answer, as returned by every part of every day.
+#[derive(Clone, Debug, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    /// Rows of a lit/unlit pixel screen that spells out the answer. Shown
+    /// as its letters when they can be read.
+    Screen(Vec<String>),
+}
+
+impl Answer {
+    /// The answer as typed into the website. Screens have to be read into
+    /// letters first.
+    pub fn submission(&self) -> Result<String> {
+        match self {
+            Answer::Screen(rows) => {
+                read_letters(rows).ok_or_else(|| anyhow!("Cannot read the letters on the screen"))
+            }
+            answer => Ok(answer.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Screen(rows) => match read_letters(rows) {
+                Some(letters) => write!(f, "{}", letters),
+                None => write!(f, "{}", rows.join("\n")),
+            },
+        }
+    }
+}
+
+/// Answers are equal when they render the same, so numbers are equal by
+/// value regardless of signedness and screens by the letters they show.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Signed(b)) | (Answer::Signed(b), Answer::Unsigned(a)) => {
+                i64::try_from(*a) == Ok(*b)
+            }
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.to_string().as_str() == *other
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::$variant(value as $target)
+            }
+        })*
+    };
+}
+
+impl_from_int!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from_int!(Signed, i64, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Answer;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(24000usize).to_string(), "24000");
+        assert_eq!(Answer::from(-3isize).to_string(), "-3");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        let screen = Answer::Screen(vec!["#..".to_string(), ".#.".to_string()]);
+        assert_eq!(screen.to_string(), "#..\n.#.");
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::Unsigned(13140), Answer::Signed(13140));
+        assert_ne!(Answer::Unsigned(u64::MAX), Answer::Signed(-1));
+        assert_eq!(Answer::Text("7".to_string()), Answer::Unsigned(7));
+        assert_eq!(Answer::from("MCD"), "MCD");
+    }
+
+    #[test]
+    fn test_submission() {
+        assert_eq!(Answer::from(45000u32).submission().unwrap(), "45000");
+        assert!(Answer::Screen(vec!["##".to_string()]).submission().is_err());
+        let rows = [".##.", "#..#", "#..#", "####", "#..#", "#..#"];
+        let screen = Answer::Screen(rows.iter().map(|row| row.to_string()).collect());
+        assert_eq!(screen.submission().unwrap(), "A");
+        assert_eq!(screen, Answer::from("A"));
+        let padded = Answer::Screen(rows.iter().map(|row| format!("{}.", row)).collect());
+        assert_eq!(padded.submission().unwrap(), "A");
+        assert_eq!(padded, screen);
+    }
+}
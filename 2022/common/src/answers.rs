@@ -0,0 +1,194 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use toml::{Table, Value};
+
+use crate::{Entry, InputSource, Part, Parts, Registry};
+
+/// Section of `answers.toml` holding the answers for the real puzzle input.
+pub const PUZZLE_SECTION: &str = "input";
+
+/// Result of comparing an answer with the known one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail(String),
+    New,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Check::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// Known answers of one day, read from its `answers.toml`. Each section is
+/// named after an input file in the day's `input/` directory, with `input`
+/// standing for the real puzzle input:
+///
+/// ```toml
+/// [example]
+/// part1 = 24000
+/// part2 = 45000
+/// ```
+#[derive(Debug, Default)]
+pub struct KnownAnswers {
+    sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+impl KnownAnswers {
+    pub fn parse(s: &str) -> Result<Self> {
+        let table: Table = s.parse()?;
+        let sections = table
+            .into_iter()
+            .map(|(name, section)| {
+                let Value::Table(section) = section else {
+                    return Err(anyhow!("Section {} is not a table", name));
+                };
+                let answers = section
+                    .into_iter()
+                    .map(|(key, value)| match value {
+                        Value::String(s) => Ok((key, s)),
+                        Value::Integer(i) => Ok((key, i.to_string())),
+                        v => Err(anyhow!("Unsupported answer {} = {}", key, v)),
+                    })
+                    .collect::<Result<BTreeMap<String, String>>>()?;
+                Ok((name, answers))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { sections })
+    }
+
+    /// Loads `answers.toml` from `dir`; a missing file has no answers.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join("answers.toml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&fs::read_to_string(&path)?)
+            .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.sections.keys().map(String::as_str)
+    }
+
+    pub fn get(&self, section: &str, part: Part) -> Option<&str> {
+        self.sections
+            .get(section)?
+            .get(&part_key(part))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, section: &str, part: Part, answer: &str) -> Check {
+        match self.get(section, part) {
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Fail(expected.to_string()),
+            None => Check::New,
+        }
+    }
+}
+
+/// Name of the `answers.toml` section that applies to `source`, if any.
+pub fn section_name(source: &InputSource) -> Option<String> {
+    match source {
+        InputSource::File(filename) => Path::new(filename)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()),
+        InputSource::Puzzle => Some(PUZZLE_SECTION.to_string()),
+        InputSource::Stdin | InputSource::Text(_) => None,
+    }
+}
+
+fn section_source(dir: &str, section: &str) -> InputSource {
+    let path: PathBuf = [dir, "input", section].iter().collect();
+    if section == PUZZLE_SECTION && !path.exists() {
+        InputSource::Puzzle
+    } else {
+        InputSource::File(path.to_string_lossy().into_owned())
+    }
+}
+
+fn verify_entry(entry: Entry) -> Result<bool> {
+    let known = KnownAnswers::load(Path::new(entry.dir))?;
+    let mut ok = true;
+    for section in known.sections() {
+        let parts = match (
+            known.get(section, Part::Part1),
+            known.get(section, Part::Part2),
+        ) {
+            (Some(_), Some(_)) => Parts::Both,
+            (Some(_), None) => Parts::Part1,
+            (None, Some(_)) => Parts::Part2,
+            (None, None) => continue,
+        };
+        let solved = section_source(entry.dir, section)
+            .read(entry.day)
+            .and_then(|input| (entry.solver)(&input, parts));
+        match solved {
+            Ok(solved) => {
+                for answer in solved.answers {
+                    let check = known.check(section, answer.part, &answer.answer.to_string());
+                    ok &= !matches!(check, Check::Fail(_));
+                    println!(
+                        "day {} {} part {}: {} {}",
+                        entry.day, section, answer.part, answer.answer, check
+                    );
+                }
+            }
+            Err(e) => {
+                ok = false;
+                println!("day {} {}: FAIL ({})", entry.day, section, e);
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Solves every known answer of every registered day. Returns whether all
+/// of them still match.
+pub fn verify_all(registry: &Registry) -> Result<bool> {
+    registry
+        .entries()
+        .try_fold(true, |ok, entry| Ok(verify_entry(entry)? && ok))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{section_name, Check, InputSource, KnownAnswers, Part};
+
+    #[test]
+    fn test_known_answers() {
+        let known = KnownAnswers::parse("[example]\npart1 = 24000\npart2 = \"CMZ\"\n").unwrap();
+        assert_eq!(known.sections().collect::<Vec<&str>>(), vec!["example"]);
+        assert_eq!(known.check("example", Part::Part1, "24000"), Check::Pass);
+        assert_eq!(
+            known.check("example", Part::Part2, "MCD"),
+            Check::Fail("CMZ".to_string())
+        );
+        assert_eq!(known.check("input", Part::Part1, "1"), Check::New);
+        assert!(KnownAnswers::parse("example = 1").is_err());
+    }
+
+    #[test]
+    fn test_section_name() {
+        assert_eq!(
+            section_name(&InputSource::File("day1/input/example".to_string())).as_deref(),
+            Some("example")
+        );
+        assert_eq!(section_name(&InputSource::Puzzle).as_deref(), Some("input"));
+        assert_eq!(section_name(&InputSource::Stdin), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fmt, hint::black_box, time::Duration};
+
+use anyhow::{anyhow, Result};
+
+use crate::{time, Part, Parts, Solution};
+
+/// A separately timed step of solving a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            min: *samples.first()?,
+            median: percentile(50),
+            p95: percentile(95),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, p95 {:?}",
+            self.min, self.median, self.p95
+        )
+    }
+}
+
+/// Benchmarks one phase of a day's puzzle.
+pub type Bencher =
+    fn(input: &str, parts: Parts, warmup: usize, iterations: usize) -> Result<Vec<(Phase, Stats)>>;
+
+/// Times `iterations` runs of `f` after `warmup` unmeasured runs.
+pub fn measure<T>(
+    warmup: usize,
+    iterations: usize,
+    mut f: impl FnMut() -> Result<T>,
+) -> Result<Stats> {
+    for _ in 0..warmup {
+        black_box(f()?);
+    }
+    let samples = (0..iterations)
+        .map(|_| {
+            let (res, elapsed) = time(&mut f);
+            black_box(res?);
+            Ok(elapsed)
+        })
+        .collect::<Result<Vec<Duration>>>()?;
+    Stats::from_samples(samples).ok_or_else(|| anyhow!("No iterations to measure"))
+}
+
+/// Runs parsing and each selected part of `S` `iterations` times after
+/// `warmup` unmeasured runs.
+pub fn bench<S: Solution>(
+    input: &str,
+    parts: Parts,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<(Phase, Stats)>> {
+    let mut res = vec![(
+        Phase::Parse,
+        measure(warmup, iterations, || S::parse(input))?,
+    )];
+    let parsed = S::parse(input)?;
+    for part in parts.parts() {
+        let stats = measure(warmup, iterations, || S::solve(&parsed, part))?;
+        res.push((Phase::Part(part), stats));
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{bench, solution::tests::Lengths, Part, Parts, Phase, Stats};
+
+    #[test]
+    fn test_stats() {
+        let stats =
+            Stats::from_samples((1..=20).rev().map(Duration::from_millis).collect()).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert!(Stats::from_samples(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_bench() {
+        let res = bench::<Lengths>("ab\ncde\n", Parts::Part2, 1, 3).unwrap();
+        let phases = res.iter().map(|(phase, _)| *phase).collect::<Vec<Phase>>();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Part(Part::Part2)]);
+        assert!(bench::<Lengths>("", Parts::Both, 0, 0).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/iisulop/adventofcode";
+
+/// Client for the Advent of Code website. Downloaded inputs are cached on
+/// disk so each one is fetched only once.
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+}
+
+fn home_dir() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("HOME is not set"))
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Result<PathBuf> {
+    match env::var_os(var) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(home_dir()?.join(fallback)),
+    }
+}
+
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(xdg_dir("XDG_CONFIG_HOME", ".config")?.join("aoc"))
+}
+
+fn read_session(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+        }
+    }
+
+    /// Reads the settings from the environment. `AOC_BASE_URL` overrides
+    /// the website address, the session token comes from `AOC_SESSION` or
+    /// `~/.config/aoc/session` and inputs are cached under `AOC_CACHE_DIR`
+    /// or `~/.cache/aoc`.
+    pub fn from_env() -> Result<Self> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => Some(session),
+            Err(_) => read_session(&config_dir()?.join("session")),
+        };
+        let cache_dir = match env::var_os("AOC_CACHE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => xdg_dir("XDG_CACHE_HOME", ".cache")?.join("aoc"),
+        };
+        Ok(Self::new(&base_url, session, cache_dir))
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    /// Returns the input for `day`, fetching it into the cache if missing.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.input_path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+        let input = self.fetch_input(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        let path = format!("/{}/day/{}/input", year, day);
+        let response = self
+            .request("GET", &path)?
+            .call()
+            .map_err(|e| anyhow!("Could not fetch {}: {}", path, e))?;
+        Ok(response.into_string()?)
+    }
+
+    pub(crate) fn request(&self, method: &str, path: &str) -> Result<ureq::Request> {
+        let session = self
+            .session
+            .as_ref()
+            .ok_or_else(|| anyhow!("No session token, set AOC_SESSION or ~/.config/aoc/session"))?;
+        Ok(ureq::request(method, &format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::{test_server::serve, Client};
+
+    #[test]
+    fn test_input_is_fetched_once() {
+        let cache_dir = env::temp_dir().join(format!("aoc-client-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let (base_url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(&base_url, Some("abc".to_string()), cache_dir.clone());
+
+        assert_eq!(client.input(2022, 5).unwrap(), "1000\n2000\n");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/5/input "));
+        assert!(requests[0].contains("session=abc"));
+        assert_eq!(
+            fs::read_to_string(cache_dir.join("2022").join("day05.txt")).unwrap(),
+            "1000\n2000\n"
+        );
+        // The server is gone, so this can only come from the cache.
+        assert_eq!(client.input(2022, 5).unwrap(), "1000\n2000\n");
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let client = Client::new("http://127.0.0.1:1", None, env::temp_dir());
+        assert!(client.input(2022, 99).is_err());
+    }
+}
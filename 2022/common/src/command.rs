@@ -0,0 +1,63 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+
+use crate::{Client, Entry, InputSource, Part, Parts, YEAR};
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Submit an answer to the website
+    Submit {
+        #[arg(value_enum)]
+        part: Part,
+        /// Answer to submit, solved from the puzzle input when omitted
+        answer: Option<String>,
+        /// Submit even if a known answer or bound says it is wrong
+        #[arg(long)]
+        force: bool,
+    },
+    /// Time parsing and the selected parts over many runs
+    Bench {
+        /// Measured runs of each phase
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        /// Unmeasured runs of each phase before measuring
+        #[arg(long, default_value_t = 10)]
+        warmup: usize,
+    },
+}
+
+/// Runs `command` for the day in `entry` on the puzzle input from `source`.
+pub fn run_command(
+    command: &Command,
+    entry: Entry,
+    source: &InputSource,
+    parts: Parts,
+) -> Result<()> {
+    match command {
+        Command::Submit {
+            part,
+            answer,
+            force,
+        } => {
+            let answer = match answer {
+                Some(answer) => answer.clone(),
+                None => (entry.solver)(&source.read(entry.day)?, (*part).into())?
+                    .answers
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow!("No answer for part {}", part))?
+                    .answer
+                    .submission()?,
+            };
+            let verdict = Client::from_env()?.submit(YEAR, entry.day, *part, &answer, *force)?;
+            println!("day {} part {}: {}: {}", entry.day, part, answer, verdict);
+        }
+        Command::Bench { iterations, warmup } => {
+            let input = source.read(entry.day)?;
+            for (phase, stats) in (entry.bench)(&input, parts, *warmup, *iterations)? {
+                println!("day {} {}: {}", entry.day, phase, stats);
+            }
+        }
+    }
+    Ok(())
+}
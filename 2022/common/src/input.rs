@@ -0,0 +1,98 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use crate::{Client, YEAR};
+
+/// Where puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    File(String),
+    Stdin,
+    Text(String),
+    /// The day's own puzzle input, from the cache or the website.
+    Puzzle,
+}
+
+impl InputSource {
+    pub fn read(&self, day: u8) -> Result<String> {
+        match self {
+            InputSource::File(filename) => read_input(File::open(filename)?),
+            InputSource::Stdin => read_input(io::stdin().lock()),
+            InputSource::Text(text) => Ok(text.clone()),
+            InputSource::Puzzle => Client::from_env()?.input(YEAR, day),
+        }
+    }
+}
+
+pub fn read_input<R: Read>(mut reader: R) -> Result<String> {
+    let mut buf = String::new();
+    reader
+        .read_to_string(&mut buf)
+        .map_err(|e| anyhow!("Could not read input: {}", e))?;
+    Ok(buf)
+}
+
+/// Command line arguments selecting the puzzle input.
+#[derive(Args, Debug)]
+pub struct InputArgs {
+    /// Input file, or `-` to read from stdin. Defaults to the cached puzzle
+    /// input, which is downloaded when missing
+    filename: Option<String>,
+    /// Literal puzzle input
+    #[arg(long, conflicts_with = "filename")]
+    input_text: Option<String>,
+}
+
+impl InputArgs {
+    pub fn source(&self) -> InputSource {
+        match (&self.filename, &self.input_text) {
+            (_, Some(text)) => InputSource::Text(text.clone()),
+            (Some(filename), None) if filename == "-" => InputSource::Stdin,
+            (Some(filename), None) => InputSource::File(filename.clone()),
+            (None, None) => InputSource::Puzzle,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use crate::{read_input, InputArgs, InputSource};
+
+    #[derive(Parser)]
+    struct Args {
+        #[command(flatten)]
+        input: InputArgs,
+    }
+
+    fn source(args: &[&str]) -> InputSource {
+        Args::try_parse_from(args).unwrap().input.source()
+    }
+
+    #[test]
+    fn test_source() {
+        assert_eq!(
+            source(&["day", "input/example"]),
+            InputSource::File("input/example".to_string())
+        );
+        assert_eq!(source(&["day", "-"]), InputSource::Stdin);
+        assert_eq!(
+            source(&["day", "--input-text", "1\n2"]),
+            InputSource::Text("1\n2".to_string())
+        );
+        assert_eq!(source(&["day"]), InputSource::Puzzle);
+        assert!(Args::try_parse_from(["day", "file", "--input-text", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(read_input("abc\n".as_bytes()).unwrap(), "abc\n");
+        assert_eq!(InputSource::Text("abc".to_string()).read(1).unwrap(), "abc");
+    }
+}
@@ -1,22 +1,189 @@
-use clap::Parser;
+use std::{fmt, sync::OnceLock};
 
-#[derive(PartialEq)]
+use clap::{Parser, ValueEnum};
+
+mod answer;
+mod answers;
+mod bench;
+mod client;
+mod command;
+mod input;
+mod ocr;
+mod registry;
+mod runner;
+mod solution;
+mod submit;
+#[cfg(test)]
+mod test_server;
+
+pub use answer::Answer;
+pub use answers::{section_name, verify_all, Check, KnownAnswers, PUZZLE_SECTION};
+pub use bench::{bench, measure, Bencher, Phase, Stats};
+pub use client::{config_dir, Client, DEFAULT_BASE_URL};
+pub use command::{run_command, Command};
+pub use input::{read_input, InputArgs, InputSource};
+pub use ocr::read_letters;
+pub use registry::{Entry, Registry, Solver};
+pub use runner::{run, run_entry, Format, Record};
+pub use solution::{solve, time, PartAnswer, Solution, Solved};
+pub use submit::{History, Verdict};
+
+pub const YEAR: u16 = 2022;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Part {
+    #[value(name = "1")]
+    Part1,
+    #[value(name = "2")]
+    Part2,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Which parts of a puzzle to run, as selected on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Parts {
+    #[value(name = "1")]
     Part1,
+    #[value(name = "2")]
     Part2,
+    #[value(alias = "all")]
+    Both,
+}
+
+impl Parts {
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            Parts::Part1 => vec![Part::Part1],
+            Parts::Part2 => vec![Part::Part2],
+            Parts::Both => vec![Part::Part1, Part::Part2],
+        }
+    }
+
+    pub fn contains(&self, part: Part) -> bool {
+        self.parts().contains(&part)
+    }
+}
+
+impl From<Part> for Parts {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::Part1 => Parts::Part1,
+            Part::Part2 => Parts::Part2,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-   filename: String,
+pub struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Parts to run
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+    /// Report how long parsing and each part take
+    #[arg(long)]
+    time: bool,
+    /// Output format of the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Args {
+    /// Where the puzzle input is read from.
+    pub fn source(&self) -> InputSource {
+        self.input.source()
+    }
+
+    pub fn parts(&self) -> Parts {
+        self.part
+    }
 }
 
-pub fn get_filename() -> String {
-    let args = Args::parse();
-    args.filename
+fn args() -> &'static Args {
+    static ARGS: OnceLock<Args> = OnceLock::new();
+    ARGS.get_or_init(Args::parse)
 }
 
 #[cfg(test)]
 mod tests {
+    use clap::Parser;
+
+    use crate::{Args, Command, Format, Part, Parts};
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Parts::Part1.parts(), vec![Part::Part1]);
+        assert_eq!(Parts::Part2.parts(), vec![Part::Part2]);
+        assert_eq!(Parts::Both.parts(), vec![Part::Part1, Part::Part2]);
+        assert!(Parts::Both.contains(Part::Part2));
+        assert!(!Parts::Part1.contains(Part::Part2));
+    }
+
+    #[test]
+    fn test_part_arg() {
+        let args = Args::try_parse_from(["day"]).unwrap();
+        assert_eq!(args.part, Parts::Both);
+        let args = Args::try_parse_from(["day", "--part", "all", "input"]).unwrap();
+        assert_eq!(args.part, Parts::Both);
+        let args = Args::try_parse_from(["day", "--part", "2", "input"]).unwrap();
+        assert_eq!(args.part, Parts::Part2);
+        assert!(Args::try_parse_from(["day", "--part", "3", "input"]).is_err());
+    }
+
+    #[test]
+    fn test_format_arg() {
+        let args = Args::try_parse_from(["day"]).unwrap();
+        assert_eq!(args.format, Format::Text);
+        let args = Args::try_parse_from(["day", "--format", "json"]).unwrap();
+        assert_eq!(args.format, Format::Json);
+    }
+
+    #[test]
+    fn test_submit_command() {
+        let args = Args::try_parse_from(["day", "submit", "2", "45000"]).unwrap();
+        match args.command {
+            Some(Command::Submit {
+                part,
+                answer,
+                force,
+            }) => {
+                assert_eq!(part, Part::Part2);
+                assert_eq!(answer.as_deref(), Some("45000"));
+                assert!(!force);
+            }
+            command => panic!("Expected submit command, got {:?}", command),
+        }
+        let args = Args::try_parse_from(["day", "input/example"]).unwrap();
+        assert!(args.command.is_none());
+    }
+
+    #[test]
+    fn test_bench_command() {
+        let args = Args::try_parse_from(["day", "--time", "bench", "--iterations", "5"]).unwrap();
+        assert!(args.time);
+        match args.command {
+            Some(Command::Bench { iterations, warmup }) => {
+                assert_eq!(iterations, 5);
+                assert_eq!(warmup, 10);
+            }
+            command => panic!("Expected bench command, got {:?}", command),
+        }
+    }
 }
@@ -0,0 +1,91 @@
+/// Height of the block font letters.
+pub const LETTER_HEIGHT: usize = 6;
+const LETTER_WIDTH: usize = 4;
+/// Letters are separated by one unlit column.
+const LETTER_STRIDE: usize = LETTER_WIDTH + 1;
+
+/// The block font letters drawn on screens by the puzzles, `#` for a lit
+/// pixel.
+const FONT: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+fn is_lit(c: char) -> bool {
+    c == '#'
+}
+
+/// Reads the letters spelled out on a screen of `#` (lit) and `.` (unlit)
+/// pixels. Returns `None` if anything on it is not a known letter.
+pub fn read_letters(rows: &[String]) -> Option<String> {
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+    let pixels: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.chars().map(is_lit).collect())
+        .collect();
+    let width = pixels.iter().map(Vec::len).max()?;
+    let lit = |row: usize, col: usize| pixels[row].get(col).copied().unwrap_or(false);
+    (0..width)
+        .step_by(LETTER_STRIDE)
+        .map(|start| {
+            if (0..LETTER_HEIGHT).any(|row| lit(row, start + LETTER_WIDTH)) {
+                return None;
+            }
+            FONT.iter()
+                .find(|(_, glyph)| {
+                    glyph.iter().enumerate().all(|(row, line)| {
+                        line.chars()
+                            .enumerate()
+                            .all(|(col, c)| is_lit(c) == lit(row, start + col))
+                    })
+                })
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocr::read_letters;
+
+    fn screen(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn test_read_letters() {
+        let rows = screen(&[
+            "####.#..#.####.###..",
+            "#....#..#....#.#..#.",
+            "###..####...#..###..",
+            "#....#..#..#...#..#.",
+            "#....#..#.#....#..#.",
+            "####.#..#.####.###..",
+        ]);
+        assert_eq!(read_letters(&rows).as_deref(), Some("EHZB"));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        let rows = screen(&["##..##..##..##..##..##..##..##..##..##.."; 6]);
+        assert_eq!(read_letters(&rows), None);
+        assert_eq!(read_letters(&screen(&["####"])), None);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::{bench, solve, Bencher, Parts, Solution, Solved};
+
+/// Solves the selected parts of one day's puzzle for the given input.
+pub type Solver = fn(input: &str, parts: Parts) -> Result<Solved>;
+
+/// A registered day: its solver and the crate directory holding its inputs
+/// and known answers.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub day: u8,
+    pub dir: &'static str,
+    pub solver: Solver,
+    pub bench: Bencher,
+}
+
+impl Entry {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            dir: S::DIR,
+            solver: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+}
+
+/// Solvers for every day, keyed by day number.
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<u8, Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S: Solution>(&mut self) {
+        self.entries.insert(S::DAY, Entry::of::<S>());
+    }
+
+    pub fn get(&self, day: u8) -> Option<Entry> {
+        self.entries.get(&day).copied()
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.entries.keys().copied()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = Entry> + '_ {
+        self.entries.values().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::{Answer, Part, Parts, Registry, Solution};
+
+    struct Echo<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Echo<DAY> {
+        const DAY: u8 = DAY;
+        const DIR: &'static str = "";
+
+        type Input = String;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer> {
+            Ok(input.as_str().into())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer> {
+            Ok(input.to_uppercase().into())
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let mut registry = Registry::new();
+        registry.register::<Echo<3>>();
+        registry.register::<Echo<1>>();
+        assert_eq!(registry.days().collect::<Vec<u8>>(), vec![1, 3]);
+        assert!(registry.get(2).is_none());
+        let entry = registry.get(3).unwrap();
+        assert_eq!(entry.day, 3);
+        let solved = (entry.solver)("abc", Parts::Part2).unwrap();
+        assert_eq!(solved.answers.len(), 1);
+        assert_eq!(solved.answers[0].part, Part::Part2);
+        assert_eq!(solved.answers[0].answer, "ABC");
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    args, run_command, section_name, Args, Entry, KnownAnswers, Part, PartAnswer, Solution, YEAR,
+};
+
+/// How answers are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    /// One JSON record per answer and line
+    Json,
+}
+
+/// A single answer as printed with `--format json`.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed_ns: u64,
+}
+
+impl Record {
+    pub fn new(day: u8, answer: &PartAnswer) -> Self {
+        Self {
+            year: YEAR,
+            day,
+            part: answer.part.number(),
+            answer: answer.answer.to_string(),
+            elapsed_ns: u64::try_from(answer.elapsed.as_nanos()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Solves the day in `entry` as selected by `args`, comparing the answers
+/// with the day's `answers.toml`.
+pub fn run_entry(entry: Entry, args: &Args) -> Result<()> {
+    let source = args.input.source();
+    if let Some(command) = &args.command {
+        return run_command(command, entry, &source, args.part);
+    }
+    let solved = (entry.solver)(&source.read(entry.day)?, args.part)?;
+    if args.format == Format::Json {
+        for answer in &solved.answers {
+            println!(
+                "{}",
+                serde_json::to_string(&Record::new(entry.day, answer))?
+            );
+        }
+        return Ok(());
+    }
+    let known = KnownAnswers::load(Path::new(entry.dir))?;
+    let section = section_name(&source);
+    if args.time {
+        println!("day {} parse: {:?}", entry.day, solved.parse_elapsed);
+    }
+    for answer in solved.answers {
+        let rendered = answer.answer.to_string();
+        let mut notes = Vec::new();
+        if let Some(section) = &section {
+            notes.push(known.check(section, answer.part, &rendered).to_string());
+        }
+        if args.time {
+            notes.push(format!("({:?})", answer.elapsed));
+        }
+        println!("{}", answer_line(entry.day, answer.part, &rendered, &notes));
+    }
+    Ok(())
+}
+
+/// Formats the answer of a part followed by `notes`. Multi-line answers
+/// start on a line of their own, the notes staying on the header line.
+fn answer_line(day: u8, part: Part, rendered: &str, notes: &[String]) -> String {
+    let notes = notes
+        .iter()
+        .map(|note| format!(" {}", note))
+        .collect::<String>();
+    if rendered.contains('\n') {
+        format!("day {} part {}:{}\n{}", day, part, notes, rendered)
+    } else {
+        format!("day {} part {}: {}{}", day, part, rendered, notes)
+    }
+}
+
+/// Entry point of the day binaries.
+pub fn run<S: Solution>() -> Result<()> {
+    run_entry(Entry::of::<S>(), args())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{runner::answer_line, Part, PartAnswer, Record};
+
+    #[test]
+    fn test_record() {
+        let answer = PartAnswer {
+            part: Part::Part2,
+            answer: "CMZ".into(),
+            elapsed: Duration::from_micros(3),
+        };
+        assert_eq!(
+            serde_json::to_string(&Record::new(5, &answer)).unwrap(),
+            r#"{"year":2022,"day":5,"part":2,"answer":"CMZ","elapsed_ns":3000}"#
+        );
+    }
+
+    #[test]
+    fn test_answer_line() {
+        let notes = vec!["PASS".to_string()];
+        assert_eq!(
+            answer_line(5, Part::Part1, "CMZ", &notes),
+            "day 5 part 1: CMZ PASS"
+        );
+        assert_eq!(
+            answer_line(10, Part::Part2, "#..\n.#.", &notes),
+            "day 10 part 2: PASS\n#..\n.#."
+        );
+        assert_eq!(
+            answer_line(10, Part::Part2, "#..\n.#.", &[]),
+            "day 10 part 2:\n#..\n.#."
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::{Answer, Part, Parts};
+
+/// The contract every day implements: parse the puzzle input once, then
+/// solve either part from the parsed representation.
+pub trait Solution {
+    const DAY: u8;
+    /// Crate directory of the day, holding its `input/` and `answers.toml`.
+    const DIR: &'static str;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    fn solve(input: &Self::Input, part: Part) -> Result<Answer> {
+        match part {
+            Part::Part1 => Self::part1(input),
+            Part::Part2 => Self::part2(input),
+        }
+    }
+}
+
+/// The answer to one part and the time it took to solve.
+#[derive(Clone, Debug)]
+pub struct PartAnswer {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Answers to the selected parts of a puzzle.
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub parse_elapsed: Duration,
+    pub answers: Vec<PartAnswer>,
+}
+
+/// Runs `f`, returning its result and how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Parses `input` and solves the selected `parts` of `S`.
+pub fn solve<S: Solution>(input: &str, parts: Parts) -> Result<Solved> {
+    let (input, parse_elapsed) = time(|| S::parse(input));
+    let input = input?;
+    let answers = parts
+        .parts()
+        .into_iter()
+        .map(|part| {
+            let (answer, elapsed) = time(|| S::solve(&input, part));
+            Ok(PartAnswer {
+                part,
+                answer: answer?,
+                elapsed,
+            })
+        })
+        .collect::<Result<Vec<PartAnswer>>>()?;
+    Ok(Solved {
+        parse_elapsed,
+        answers,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use anyhow::Result;
+
+    use crate::{solve, Answer, Part, Parts, Solution};
+
+    pub struct Lengths;
+
+    impl Solution for Lengths {
+        const DAY: u8 = 0;
+        const DIR: &'static str = "";
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer> {
+            Ok(input.iter().map(|l| l.len()).sum::<usize>().into())
+        }
+    }
+
+    fn answers(input: &str, parts: Parts) -> Vec<(Part, Answer)> {
+        solve::<Lengths>(input, parts)
+            .unwrap()
+            .answers
+            .into_iter()
+            .map(|a| (a.part, a.answer))
+            .collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            answers("ab\ncde\n", Parts::Both),
+            vec![(Part::Part1, 2usize.into()), (Part::Part2, 5usize.into())]
+        );
+        assert_eq!(
+            answers("ab\ncde\n", Parts::Part2),
+            vec![(Part::Part2, Answer::Unsigned(5))]
+        );
+    }
+}
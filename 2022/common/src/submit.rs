@@ -0,0 +1,345 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{Client, Part};
+
+/// The website's response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too recently, retry after this many seconds.
+    Wait(u64),
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "right answer"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::TooHigh => write!(f, "wrong answer, too high"),
+            Verdict::TooLow => write!(f, "wrong answer, too low"),
+            Verdict::Wait(secs) => write!(f, "answered too recently, wait {}s", secs),
+            Verdict::WrongLevel => write!(f, "part already solved or locked"),
+        }
+    }
+}
+
+/// Parses a wait time like `1m 5s` or `34s` into seconds.
+fn parse_wait(text: &str) -> Option<u64> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace().try_fold(0, |secs, el| {
+        let (num, unit) = el.split_at(el.find(|c: char| !c.is_ascii_digit())?);
+        let num: u64 = num.parse().ok()?;
+        match unit {
+            "h" => Some(secs + num * 3600),
+            "m" => Some(secs + num * 60),
+            "s" => Some(secs + num),
+            _ => None,
+        }
+    })
+}
+
+impl Verdict {
+    pub fn parse(html: &str) -> Result<Self> {
+        if html.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                Ok(Verdict::TooHigh)
+            } else if html.contains("your answer is too low") {
+                Ok(Verdict::TooLow)
+            } else {
+                Ok(Verdict::Wrong)
+            }
+        } else if html.contains("You gave an answer too recently") {
+            parse_wait(html)
+                .map(Verdict::Wait)
+                .ok_or_else(|| anyhow!("Could not read wait time from response"))
+        } else if html.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::WrongLevel)
+        } else {
+            Err(anyhow!("Unexpected response to submission"))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wait(_) => "wait",
+            Verdict::WrongLevel => "wrong-level",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Submission {
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Outcomes of earlier submissions for one day, stored one per line as
+/// `<part> <verdict> <answer>` next to the cached input.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+    wait_until: u64,
+}
+
+fn parse_part(s: &str) -> Option<Part> {
+    match s {
+        "1" => Some(Part::Part1),
+        "2" => Some(Part::Part2),
+        _ => None,
+    }
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut history = Self {
+            path: path.to_path_buf(),
+            submissions: Vec::new(),
+            wait_until: 0,
+        };
+        if !path.exists() {
+            return Ok(history);
+        }
+        for line in fs::read_to_string(path)?.lines() {
+            match line.splitn(3, ' ').collect::<Vec<&str>>().as_slice() {
+                ["wait", until] => history.wait_until = until.parse()?,
+                [part, verdict, answer] => history.submissions.push(Submission {
+                    part: parse_part(part).ok_or_else(|| anyhow!("Unknown part {}", part))?,
+                    answer: answer.to_string(),
+                    verdict: Verdict::from_name(verdict)
+                        .ok_or_else(|| anyhow!("Unknown verdict {}", verdict))?,
+                }),
+                _ => return Err(anyhow!("Could not parse history line: {}", line)),
+            }
+        }
+        Ok(history)
+    }
+
+    fn save(&self) -> Result<()> {
+        let mut contents = format!("wait {}\n", self.wait_until);
+        for submission in &self.submissions {
+            contents.push_str(&format!(
+                "{} {} {}\n",
+                submission.part,
+                submission.verdict.name(),
+                submission.answer
+            ));
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    /// Checks `answer` against what is already known about `part`. Fails
+    /// when submitting it would be pointless; with `force` only known-wrong
+    /// answers and the rate limit are enforced.
+    pub fn check(&self, part: Part, answer: &str, now: u64, force: bool) -> Result<()> {
+        if now < self.wait_until {
+            return Err(anyhow!(
+                "Answered too recently, wait {}s",
+                self.wait_until - now
+            ));
+        }
+        for submission in self.submissions.iter().filter(|s| s.part == part) {
+            if submission.answer == answer {
+                return Err(anyhow!("Already submitted: {}", submission.verdict));
+            }
+            if submission.verdict == Verdict::Correct && !force {
+                return Err(anyhow!(
+                    "Part {} was already solved with {}",
+                    part,
+                    submission.answer
+                ));
+            }
+        }
+        if let (Ok(value), false) = (answer.parse::<i64>(), force) {
+            if let Some(bound) = self.bound(part, Verdict::TooHigh, i64::min) {
+                if value >= bound {
+                    return Err(anyhow!("{} is not below known too high {}", value, bound));
+                }
+            }
+            if let Some(bound) = self.bound(part, Verdict::TooLow, i64::max) {
+                if value <= bound {
+                    return Err(anyhow!("{} is not above known too low {}", value, bound));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn bound(&self, part: Part, verdict: Verdict, pick: fn(i64, i64) -> i64) -> Option<i64> {
+        self.submissions
+            .iter()
+            .filter(|s| s.part == part && s.verdict == verdict)
+            .filter_map(|s| s.answer.parse().ok())
+            .reduce(pick)
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: &Verdict, now: u64) -> Result<()> {
+        match verdict {
+            Verdict::Wait(secs) => self.wait_until = now + secs,
+            Verdict::WrongLevel => (),
+            _ => self.submissions.push(Submission {
+                part,
+                answer: answer.to_string(),
+                verdict: verdict.clone(),
+            }),
+        }
+        self.save()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Client {
+    pub fn history_path(&self, year: u16, day: u8) -> PathBuf {
+        self.input_path(year, day).with_extension("answers")
+    }
+
+    /// Submits `answer` unless the local history shows it to be pointless,
+    /// and records the website's verdict.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        force: bool,
+    ) -> Result<Verdict> {
+        let mut history = History::load(&self.history_path(year, day))?;
+        history.check(part, answer, now(), force)?;
+        let path = format!("/{}/day/{}/answer", year, day);
+        let response = self
+            .request("POST", &path)?
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| anyhow!("Could not submit to {}: {}", path, e))?;
+        let verdict = Verdict::parse(&response.into_string()?)?;
+        history.record(part, answer, &verdict, now())?;
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::{test_server::serve, Client, History, Part, Verdict};
+
+    #[test]
+    fn test_parse_verdict() {
+        let parse = |html| Verdict::parse(html).unwrap();
+        assert_eq!(
+            parse("<p>That's the right answer! You are one gold star closer"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse("<p>That's not the right answer.  If you're stuck"),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse("<p>That's not the right answer; your answer is too high.  If"),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse("<p>That's not the right answer; your answer is too low.  If"),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            Verdict::Wait(65)
+        );
+        assert_eq!(
+            parse("<p>You don't seem to be solving the right level.  Did you already complete it?"),
+            Verdict::WrongLevel
+        );
+        assert!(Verdict::parse("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_history() {
+        let path = env::temp_dir().join(format!("aoc-history-test-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(Part::Part1, "100", &Verdict::TooHigh, 0)
+            .unwrap();
+        history
+            .record(Part::Part1, "10", &Verdict::TooLow, 0)
+            .unwrap();
+        history
+            .record(Part::Part1, "50", &Verdict::Wrong, 0)
+            .unwrap();
+        history
+            .record(Part::Part1, "40", &Verdict::Wait(60), 0)
+            .unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert!(history.check(Part::Part1, "40", 30, false).is_err());
+        assert!(history.check(Part::Part1, "40", 60, false).is_ok());
+        assert!(history.check(Part::Part1, "50", 60, false).is_err());
+        assert!(history.check(Part::Part1, "100", 60, true).is_err());
+        assert!(history.check(Part::Part1, "120", 60, false).is_err());
+        assert!(history.check(Part::Part1, "120", 60, true).is_ok());
+        assert!(history.check(Part::Part1, "5", 60, false).is_err());
+        assert!(history.check(Part::Part2, "5", 60, false).is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let cache_dir = env::temp_dir().join(format!("aoc-submit-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let (base_url, server) = serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        )]);
+        let client = Client::new(&base_url, Some("abc".to_string()), cache_dir.clone());
+
+        assert_eq!(
+            client.submit(2022, 1, Part::Part2, "45", false).unwrap(),
+            Verdict::TooLow
+        );
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer "));
+        assert!(requests[0].ends_with("level=2&answer=45"));
+        assert!(client.submit(2022, 1, Part::Part2, "45", false).is_err());
+        assert!(client.submit(2022, 1, Part::Part2, "44", false).is_err());
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Error, Result};
+
+use common::{Answer, Solution};
+
+pub type Elves = Vec<Vec<usize>>;
+
+fn read_elves(input: &str) -> Result<Elves, Error> {
+    let mut elves = Vec::new();
+    let mut carrying: Vec<usize> = Vec::new();
+    for line in input.lines() {
+        if line.is_empty() {
+            elves.push(carrying);
+            carrying = Vec::new();
+        } else {
+            carrying.push(line.trim().parse()?);
+        }
+    }
+    if !carrying.is_empty() {
+        elves.push(carrying);
+    }
+    Ok(elves)
+}
+
+fn count_carrying(elves: &Elves) -> Vec<usize> {
+    elves.iter().map(|elf| elf.iter().sum()).collect()
+}
+
+fn get_max_count(elves: &Elves) -> Result<usize, Error> {
+    let counts = count_carrying(elves);
+    counts.into_iter().max().ok_or_else(|| anyhow!("No counts"))
+}
+
+fn get_max_3_sum(elves: &Elves) -> usize {
+    let counts = count_carrying(elves);
+    counts
+        .into_iter()
+        .fold(Vec::with_capacity(3), |mut coll: Vec<usize>, el| {
+            if coll.len() < 3 {
+                coll.push(el);
+            } else {
+                for old_max in coll.iter_mut() {
+                    if el > *old_max {
+                        *old_max = el;
+                        break;
+                    }
+                }
+            }
+            coll.sort();
+            coll
+        })
+        .iter()
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Elves;
+
+    fn parse(input: &str) -> Result<Elves> {
+        read_elves(input)
+    }
+
+    fn part1(elves: &Elves) -> Result<Answer> {
+        Ok(get_max_count(elves)?.into())
+    }
+
+    fn part2(elves: &Elves) -> Result<Answer> {
+        Ok(get_max_3_sum(elves).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{get_max_3_sum, get_max_count, read_elves};
+
+    #[test]
+    fn test_part1() {
+        let input = read_elves(&read_to_string("input/example").unwrap()).unwrap();
+        let max_count = get_max_count(&input).unwrap();
+        assert_eq!(max_count, 24000)
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_elves(&read_to_string("input/example").unwrap()).unwrap();
+        let max_sum = get_max_3_sum(&input);
+        assert_eq!(max_sum, 45000)
+    }
+
+    #[test]
+    fn test_no_trailing_blank_line() {
+        let example = read_to_string("input/example").unwrap();
+        let input = read_elves(example.trim_end()).unwrap();
+        assert_eq!(input.len(), 5);
+        assert_eq!(get_max_count(&input).unwrap(), 24000);
+        assert_eq!(get_max_3_sum(&input), 45000);
+    }
+}
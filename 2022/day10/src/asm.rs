@@ -0,0 +1,164 @@
+use std::{collections::HashMap, fmt};
+
+use anyhow::{anyhow, Result};
+
+use crate::{Cpu, InstructionSet, Operation};
+
+/// Characters starting a comment that runs to the end of the line.
+const COMMENT_CHARS: [char; 2] = [';', '#'];
+/// Limit on the cycles run to annotate a program, which may loop forever.
+const DISASSEMBLY_CYCLES: usize = 100_000;
+/// Starts listed for each instruction, later ones are only counted.
+const LISTED_STARTS: usize = 3;
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.instruction.name)?;
+        for operand in &self.operands {
+            write!(f, " {}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+struct Statement<'a> {
+    line: usize,
+    name: &'a str,
+    operands: Vec<&'a str>,
+}
+
+/// Assembles `source` into a program. A line holds an instruction, optionally
+/// preceded by labels ending in `:` and followed by a comment starting with
+/// `;` or `#`. An operand naming a label stands for the distance from its
+/// instruction to the label, as taken by relative jumps.
+pub fn assemble(set: &InstructionSet, source: &str) -> Result<Vec<Operation>> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+        let code = line.split(COMMENT_CHARS).next().unwrap_or_default();
+        let mut words = code.split_whitespace().peekable();
+        while let Some(label) = words.peek().and_then(|word| word.strip_suffix(':')) {
+            if labels.insert(label, statements.len()).is_some() {
+                return Err(anyhow!("Line {}: duplicate label {}", idx + 1, label));
+            }
+            words.next();
+        }
+        if let Some(name) = words.next() {
+            statements.push(Statement {
+                line: idx + 1,
+                name,
+                operands: words.collect(),
+            });
+        }
+    }
+    statements
+        .iter()
+        .enumerate()
+        .map(|(pc, statement)| {
+            statement
+                .operands
+                .iter()
+                .map(|operand| match labels.get(operand) {
+                    Some(target) => Ok(*target as isize - pc as isize),
+                    None => operand
+                        .parse()
+                        .map_err(|_| anyhow!("Unknown label {}", operand)),
+                })
+                .collect::<Result<Vec<isize>>>()
+                .and_then(|operands| set.operation(statement.name, operands))
+                .map_err(|e| anyhow!("Line {}: {}", statement.line, e))
+        })
+        .collect()
+}
+
+/// Lists `program` with the first cycles each instruction starts on and the
+/// value of X during them, as comments so the listing assembles again.
+pub fn disassemble(program: &[Operation]) -> String {
+    let mut starts = vec![Vec::new(); program.len()];
+    let mut more = vec![0; program.len()];
+    for tick in Cpu::new(program)
+        .take(DISASSEMBLY_CYCLES)
+        .filter(|tick| tick.step == 0)
+    {
+        if starts[tick.pc].len() < LISTED_STARTS {
+            starts[tick.pc].push(format!("cycle {} x {}", tick.cycle, tick.x));
+        } else {
+            more[tick.pc] += 1;
+        }
+    }
+    let lines = program
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    let width = lines.iter().map(String::len).max().unwrap_or(0);
+    lines
+        .iter()
+        .zip(starts.iter().zip(more))
+        .map(|(line, (starts, more))| {
+            let note = match (starts.is_empty(), more) {
+                (true, _) => "never run".to_string(),
+                (false, 0) => starts.join(", "),
+                (false, more) => format!("{} and {} more", starts.join(", "), more),
+            };
+            format!("{:width$} ; {}\n", line, note, width = width)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::read_to_string, path::Path};
+
+    use crate::{assemble, disassemble, InstructionSet};
+
+    #[test]
+    fn test_assemble() {
+        let set = InstructionSet::load(Path::new("extended.toml")).unwrap();
+        let source = "\
+# jumps to labels
+        addx 2       ; x = 3
+loop:   addx -1
+        jmp done     # forward
+        jmp loop     ; backward
+done:   noop
+";
+        let program = assemble(&set, source).unwrap();
+        let listing = program
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            listing,
+            vec!["addx 2", "addx -1", "jmp 2", "jmp -2", "noop"]
+        );
+        assert!(assemble(&set, "jmp nowhere").is_err());
+        assert!(assemble(&set, "a: noop\na: noop").is_err());
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let set = InstructionSet::default();
+        let program = assemble(&set, "noop\naddx 3\naddx -5\n").unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "noop    ; cycle 1 x 1\naddx 3  ; cycle 2 x 1\naddx -5 ; cycle 4 x 4\n"
+        );
+    }
+
+    #[test]
+    fn test_disassemble_loop() {
+        let set = InstructionSet::load(Path::new("extended.toml")).unwrap();
+        let program = assemble(&set, "l: jmp l\n").unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "jmp 0 ; cycle 1 x 1, cycle 2 x 1, cycle 3 x 1 and 99997 more\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let set = InstructionSet::default();
+        let program = assemble(&set, &read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(assemble(&set, &disassemble(&program)).unwrap(), program);
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use crate::{Effect, Operation};
+
+/// The state of the CPU during one cycle, before the instruction being
+/// executed has taken effect.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tick<'a> {
+    pub cycle: usize,
+    pub x: isize,
+    /// Index of the instruction in the program.
+    pub pc: usize,
+    /// Cycles of the instruction that already passed, 0 on its first cycle.
+    pub step: usize,
+    pub instruction: &'a Operation,
+}
+
+pub type Observer<'a> = Box<dyn FnMut(&Tick<'a>) + 'a>;
+pub type Breakpoint<'a> = Box<dyn Fn(&Tick<'a>) -> bool + 'a>;
+
+/// Executes a program cycle by cycle, each instruction taking effect at the
+/// end of its last cycle. Iterating yields one [`Tick`] per cycle. Registers
+/// are machine words: arithmetic wraps around on overflow.
+pub struct Cpu<'a> {
+    program: &'a [Operation],
+    pc: usize,
+    registers: BTreeMap<String, isize>,
+    cycle: usize,
+    /// Cycles left until the current instruction completes.
+    remaining: usize,
+    observers: Vec<Observer<'a>>,
+    breakpoints: Vec<Breakpoint<'a>>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(program: &'a [Operation]) -> Self {
+        Self {
+            program,
+            pc: 0,
+            registers: BTreeMap::from([("x".to_string(), 1)]),
+            cycle: 0,
+            remaining: 0,
+            observers: Vec::new(),
+            breakpoints: Vec::new(),
+        }
+    }
+
+    pub fn x(&self) -> isize {
+        self.register("x")
+    }
+
+    /// Value of the register `name`; registers other than X start at 0.
+    pub fn register(&self, name: &str) -> isize {
+        self.registers.get(name).copied().unwrap_or(0)
+    }
+
+    /// Number of cycles executed so far.
+    pub fn cycles(&self) -> usize {
+        self.cycle
+    }
+
+    /// Calls `observer` with every executed cycle.
+    pub fn add_observer(&mut self, observer: impl FnMut(&Tick<'a>) + 'a) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Makes [`Cpu::resume`] stop at cycles matching `breakpoint`.
+    pub fn add_breakpoint(&mut self, breakpoint: impl Fn(&Tick<'a>) -> bool + 'a) {
+        self.breakpoints.push(Box::new(breakpoint));
+    }
+
+    /// Runs until a cycle hits a breakpoint, returning that cycle, or until
+    /// the program ends.
+    pub fn resume(&mut self) -> Option<Tick<'a>> {
+        while let Some(tick) = self.next() {
+            if self.breakpoints.iter().any(|breakpoint| breakpoint(&tick)) {
+                return Some(tick);
+            }
+        }
+        None
+    }
+
+    fn execute(&mut self, operation: &Operation) {
+        let operand = operation.operands.first().copied().unwrap_or(0);
+        match &operation.instruction.effect {
+            Effect::None => (),
+            Effect::Add { register } => {
+                let value = self.registers.entry(register.clone()).or_default();
+                *value = value.wrapping_add(operand);
+            }
+            Effect::Mul { register } => {
+                let value = self.registers.entry(register.clone()).or_default();
+                *value = value.wrapping_mul(operand);
+            }
+            Effect::Jump => {
+                // Jumping out of the program ends it
+                self.pc = self
+                    .pc
+                    .checked_add_signed(operand)
+                    .unwrap_or(self.program.len());
+                return;
+            }
+        }
+        self.pc += 1;
+    }
+}
+
+impl<'a> Iterator for Cpu<'a> {
+    type Item = Tick<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.program.get(self.pc)?;
+        if self.remaining == 0 {
+            self.remaining = instruction.latency().max(1);
+        }
+        self.cycle += 1;
+        let tick = Tick {
+            cycle: self.cycle,
+            x: self.x(),
+            pc: self.pc,
+            step: instruction.latency().max(1) - self.remaining,
+            instruction,
+        };
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.execute(instruction);
+        }
+        for observer in &mut self.observers {
+            observer(&tick);
+        }
+        Some(tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, path::Path};
+
+    use crate::{assemble, cpu::Cpu, read_operations, InstructionSet};
+
+    const PROGRAM: &str = "noop\naddx 3\naddx -5\n";
+
+    #[test]
+    fn test_trace() {
+        let program = read_operations(PROGRAM).unwrap();
+        let mut cpu = Cpu::new(&program);
+        let trace = cpu
+            .by_ref()
+            .map(|tick| (tick.cycle, tick.x))
+            .collect::<Vec<(usize, isize)>>();
+        assert_eq!(trace, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(cpu.x(), -1);
+        assert_eq!(cpu.cycles(), 5);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let program = read_operations(PROGRAM).unwrap();
+        let observed = Cell::new(0);
+        let mut cpu = Cpu::new(&program);
+        cpu.add_observer(|_| observed.set(observed.get() + 1));
+        cpu.add_breakpoint(|tick| tick.x == 4);
+        let tick = cpu.resume().unwrap();
+        assert_eq!((tick.cycle, tick.instruction), (4, &program[2]));
+        assert_eq!((tick.pc, tick.step), (2, 0));
+        assert_eq!(observed.get(), 4);
+        assert_eq!(cpu.resume().map(|tick| tick.cycle), Some(5));
+        assert_eq!(cpu.resume(), None);
+        assert_eq!(observed.get(), 5);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let program = read_operations(&format!("addx {}\naddx 1\n", isize::MAX - 1)).unwrap();
+        let mut cpu = Cpu::new(&program);
+        assert_eq!(cpu.by_ref().count(), 4);
+        assert_eq!(cpu.x(), isize::MIN);
+
+        let set = InstructionSet::load(Path::new("extended.toml")).unwrap();
+        let program = assemble(&set, &format!("mulx {}\nmulx 2\n", isize::MAX)).unwrap();
+        let mut cpu = Cpu::new(&program);
+        assert_eq!(cpu.by_ref().count(), 6);
+        assert_eq!(cpu.x(), -2);
+    }
+}
@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::Operation;
+
+/// What an instruction does when it completes, using its first operand.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Effect {
+    None,
+    /// Adds the operand to the register.
+    Add {
+        register: String,
+    },
+    /// Multiplies the register by the operand.
+    Mul {
+        register: String,
+    },
+    /// Moves the program counter by the operand, relative to the instruction.
+    Jump,
+}
+
+/// The definition of an instruction.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Instruction {
+    pub name: String,
+    #[serde(default)]
+    pub operands: usize,
+    pub cycles: usize,
+    pub effect: Effect,
+}
+
+impl Instruction {
+    fn new(name: &str, operands: usize, cycles: usize, effect: Effect) -> Self {
+        Self {
+            name: name.to_string(),
+            operands,
+            cycles,
+            effect,
+        }
+    }
+}
+
+/// Instructions a program may use, by name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionSet {
+    instructions: BTreeMap<String, Instruction>,
+}
+
+/// Layout of an instruction set file:
+///
+/// ```toml
+/// [[instruction]]
+/// name = "addy"
+/// operands = 1
+/// cycles = 2
+/// effect = { kind = "add", register = "y" }
+/// ```
+#[derive(Deserialize)]
+struct Config {
+    #[serde(default)]
+    instruction: Vec<Instruction>,
+}
+
+impl Default for InstructionSet {
+    /// The instructions of the puzzle.
+    fn default() -> Self {
+        let mut set = Self {
+            instructions: BTreeMap::new(),
+        };
+        for instruction in [
+            Instruction::new("noop", 0, 1, Effect::None),
+            Instruction::new(
+                "addx",
+                1,
+                2,
+                Effect::Add {
+                    register: "x".to_string(),
+                },
+            ),
+        ] {
+            set.insert(instruction).unwrap();
+        }
+        set
+    }
+}
+
+impl InstructionSet {
+    /// Adds `instruction`, replacing any instruction of the same name.
+    pub fn insert(&mut self, instruction: Instruction) -> Result<()> {
+        if instruction.cycles == 0 {
+            return Err(anyhow!("Instruction {} takes no cycles", instruction.name));
+        }
+        if instruction.effect != Effect::None && instruction.operands == 0 {
+            return Err(anyhow!("Instruction {} needs an operand", instruction.name));
+        }
+        self.instructions
+            .insert(instruction.name.clone(), instruction);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Instruction> {
+        self.instructions.get(name)
+    }
+
+    /// The puzzle's instructions extended with those in `config`.
+    pub fn parse(config: &str) -> Result<Self> {
+        let config: Config = toml::from_str(config)?;
+        let mut set = Self::default();
+        for instruction in config.instruction {
+            set.insert(instruction)?;
+        }
+        Ok(set)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|config| Self::parse(&config))
+            .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))
+    }
+
+    /// The instruction `name` applied to `operands`.
+    pub fn operation(&self, name: &str, operands: Vec<isize>) -> Result<Operation> {
+        let instruction = self
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown operation {}", name))?;
+        if operands.len() != instruction.operands {
+            return Err(anyhow!(
+                "{} takes {} operands, got {}",
+                name,
+                instruction.operands,
+                operands.len()
+            ));
+        }
+        Ok(Operation {
+            instruction: instruction.clone(),
+            operands,
+        })
+    }
+
+    pub fn parse_operation(&self, s: &str) -> Result<Operation> {
+        let mut words = s.split_whitespace();
+        let name = words.next().ok_or_else(|| anyhow!("Empty operation"))?;
+        let operands = words
+            .map(|word| word.parse::<isize>())
+            .collect::<Result<Vec<isize>, _>>()?;
+        self.operation(name, operands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{assemble, Cpu, InstructionSet};
+
+    #[test]
+    fn test_default() {
+        let set = InstructionSet::default();
+        let operation = set.parse_operation("addx -5").unwrap();
+        assert_eq!((operation.latency(), operation.operands), (2, vec![-5]));
+        assert!(set.parse_operation("addx").is_err());
+        assert!(set.parse_operation("noop 1").is_err());
+        assert!(set.parse_operation("addy 1").is_err());
+    }
+
+    #[test]
+    fn test_extended() {
+        let set = InstructionSet::load(Path::new("extended.toml")).unwrap();
+        let program = assemble(&set, "addy 3\nmulx 4\njmp 2\naddx 100\naddx -1\n").unwrap();
+        let mut cpu = Cpu::new(&program);
+        let cycles = cpu.by_ref().count();
+        assert_eq!((cpu.x(), cpu.register("y")), (3, 3));
+        assert_eq!(cycles, 2 + 3 + 1 + 2);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = "[[instruction]]\nname = \"addz\"\ncycles = 1\neffect = { kind = \"add\", register = \"z\" }\n";
+        assert!(InstructionSet::parse(config).is_err());
+        let config = "[[instruction]]\nname = \"wait\"\ncycles = 0\neffect = { kind = \"none\" }\n";
+        assert!(InstructionSet::parse(config).is_err());
+    }
+}
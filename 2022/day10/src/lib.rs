@@ -0,0 +1,151 @@
+use std::{str::FromStr, sync::OnceLock};
+
+use anyhow::{anyhow, Error, Result};
+
+use common::{Answer, Solution};
+
+mod asm;
+mod cpu;
+mod isa;
+
+pub use asm::{assemble, disassemble};
+pub use cpu::{Breakpoint, Cpu, Observer, Tick};
+pub use isa::{Effect, Instruction, InstructionSet};
+
+/// An instruction of a program along with its operands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operation {
+    pub instruction: Instruction,
+    pub operands: Vec<isize>,
+}
+
+impl Operation {
+    /// Number of cycles the operation takes to complete.
+    pub fn latency(&self) -> usize {
+        self.instruction.cycles
+    }
+}
+
+static INSTRUCTION_SET: OnceLock<InstructionSet> = OnceLock::new();
+
+/// Makes programs use `set` instead of the puzzle's instructions. Can only
+/// be done once, before any program is read.
+pub fn set_instruction_set(set: InstructionSet) -> Result<()> {
+    INSTRUCTION_SET
+        .set(set)
+        .map_err(|_| anyhow!("Instruction set is already in use"))
+}
+
+pub fn instruction_set() -> &'static InstructionSet {
+    INSTRUCTION_SET.get_or_init(InstructionSet::default)
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        instruction_set().parse_operation(s)
+    }
+}
+
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+/// Last cycle whose signal strength counts, so that looping programs end.
+const LAST_SIGNAL_CYCLE: usize = 220;
+
+fn read_operations(input: &str) -> Result<Vec<Operation>> {
+    assemble(instruction_set(), input)
+}
+
+fn calculate_signal_strength(tick: &Tick) -> isize {
+    tick.cycle as isize * tick.x
+}
+
+fn sum_of_signal_strengths(operations: &[Operation]) -> isize {
+    Cpu::new(operations)
+        .take_while(|tick| tick.cycle <= LAST_SIGNAL_CYCLE)
+        .filter(|tick| tick.cycle % 40 == 20)
+        .map(|tick| calculate_signal_strength(&tick))
+        .sum()
+}
+
+/// Draws the CRT rows, lighting a pixel when the 3 pixel wide sprite
+/// centered on X covers it during that cycle.
+fn draw_crt(operations: &[Operation]) -> Vec<String> {
+    let pixels = Cpu::new(operations)
+        .take(CRT_WIDTH * CRT_HEIGHT)
+        .map(|tick| {
+            let col = (tick.cycle - 1) % CRT_WIDTH;
+            if (col as isize - tick.x).abs() <= 1 {
+                '#'
+            } else {
+                '.'
+            }
+        })
+        .collect::<Vec<char>>();
+    pixels
+        .chunks(CRT_WIDTH)
+        .map(|row| row.iter().collect())
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<Operation>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_operations(input)
+    }
+
+    fn part1(operations: &Self::Input) -> Result<Answer> {
+        Ok(sum_of_signal_strengths(operations).into())
+    }
+
+    fn part2(operations: &Self::Input) -> Result<Answer> {
+        Ok(Answer::Screen(draw_crt(operations)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::read_to_string, path::Path};
+
+    use crate::{assemble, draw_crt, read_operations, sum_of_signal_strengths, InstructionSet};
+
+    #[test]
+    fn test_part1() {
+        let input = read_operations(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(sum_of_signal_strengths(&input), 13140);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_operations(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(
+            draw_crt(&input),
+            vec![
+                "##..##..##..##..##..##..##..##..##..##..",
+                "###...###...###...###...###...###...###.",
+                "####....####....####....####....####....",
+                "#####.....#####.....#####.....#####.....",
+                "######......######......######......####",
+                "#######.......#######.......#######.....",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_looping_program() {
+        let set = InstructionSet::load(Path::new("extended.toml")).unwrap();
+        let program = assemble(&set, "noop\nl: jmp l\n").unwrap();
+        assert_eq!(
+            sum_of_signal_strengths(&program),
+            20 + 60 + 100 + 140 + 180 + 220
+        );
+        assert_eq!(draw_crt(&program).len(), 6);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+
+use common::{Answer, Solution};
+
+pub type Rucksack = (HashSet<char>, HashSet<char>);
+pub type Rucksacks = Vec<Rucksack>;
+
+trait Priority {
+    fn priority(&self) -> Result<usize>;
+}
+
+impl Priority for char {
+    fn priority(&self) -> Result<usize> {
+        match (self.is_ascii_uppercase(), self.is_ascii_lowercase()) {
+            (false, true) => Ok(*self as usize - 97 + 1),
+            (true, false) => Ok(*self as usize - 65 + 27),
+            (_, _) => Err(anyhow!("Unexpected item type")),
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Result<Rucksack> {
+    Ok((
+        line.chars().take(line.len() / 2).collect(),
+        line.chars().skip(line.len() / 2).collect(),
+    ))
+}
+
+fn read_rucksacks(input: &str) -> Result<Rucksacks> {
+    let rucksacks = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_line)
+        .collect::<Result<Rucksacks>>()?;
+    Ok(rucksacks)
+}
+
+fn calculate_priorities_part1(rucksacks: &Rucksacks) -> Result<usize> {
+    Ok(rucksacks
+        .iter()
+        .flat_map(|sack| sack.0.intersection(&sack.1).map(|el| el.priority()))
+        .collect::<Result<Vec<usize>>>()?
+        .iter()
+        .sum())
+}
+
+fn calculate_priorities_part2(rucksacks: &Rucksacks) -> Result<usize> {
+    let res = rucksacks
+        .iter()
+        .map(|(a, b)| a.union(b).collect::<HashSet<&char>>())
+        .collect::<Vec<HashSet<&char>>>();
+    let res = res
+        .chunks(3)
+        .map(|chunk| {
+            let [a, b, c]: &[HashSet<&char>; 3] = chunk.try_into()?;
+            let bc = b.intersection(c).cloned().collect::<HashSet<&char>>();
+            Ok(a.intersection(&bc).cloned().collect::<HashSet<&char>>())
+        })
+        .collect::<Result<Vec<HashSet<&char>>>>()?;
+    let res = res
+        .iter()
+        .map(|rucksack_badge| {
+            Ok(rucksack_badge
+                .iter()
+                .map(|badge| badge.priority())
+                .collect::<Result<Vec<usize>>>()?
+                .iter()
+                .sum::<usize>())
+        })
+        .collect::<Result<Vec<usize>>>()?
+        .iter()
+        .sum();
+    Ok(res)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Rucksacks;
+
+    fn parse(input: &str) -> Result<Rucksacks> {
+        read_rucksacks(input)
+    }
+
+    fn part1(rucksacks: &Rucksacks) -> Result<Answer> {
+        Ok(calculate_priorities_part1(rucksacks)?.into())
+    }
+
+    fn part2(rucksacks: &Rucksacks) -> Result<Answer> {
+        Ok(calculate_priorities_part2(rucksacks)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{calculate_priorities_part1, calculate_priorities_part2, read_rucksacks};
+
+    #[test]
+    fn test_part1() {
+        let input = read_rucksacks(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(calculate_priorities_part1(&input).unwrap(), 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_rucksacks(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(calculate_priorities_part2(&input).unwrap(), 70);
+    }
+}
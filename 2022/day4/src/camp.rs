@@ -0,0 +1,200 @@
+use std::fmt;
+
+use crate::{SectionSet, SectionUnion, Sections};
+
+/// One elf of the assignment pairs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    /// Position of the pair in the assignments, starting at 0.
+    pub pair: usize,
+    /// 0 for the first elf of the pair, 1 for the second.
+    pub member: usize,
+    pub sections: SectionSet,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pair {} elf {} ({})",
+            self.pair + 1,
+            self.member + 1,
+            self.sections
+        )
+    }
+}
+
+/// Every elf of `assignments`, pair by pair.
+pub fn elves(assignments: &[Sections]) -> Vec<Elf> {
+    assignments
+        .iter()
+        .enumerate()
+        .flat_map(|(pair, (el1, el2))| {
+            [el1, el2]
+                .into_iter()
+                .enumerate()
+                .map(move |(member, sections)| Elf {
+                    pair,
+                    member,
+                    sections: *sections,
+                })
+        })
+        .collect()
+}
+
+/// How many elves each section is assigned to, as runs of sections with the
+/// same count, lowest sections first. Runs between the lowest and the
+/// highest assigned section may have a count of 0.
+pub fn coverage(elves: &[Elf]) -> Vec<(SectionSet, usize)> {
+    // The count changes by the delta at each position; a set ending at
+    // usize::MAX has no position after it and stays open to the end
+    let mut events = elves
+        .iter()
+        .flat_map(|elf| {
+            let after = elf.sections.end.checked_add(1);
+            [
+                Some((elf.sections.start, 1)),
+                after.map(|after| (after, -1)),
+            ]
+        })
+        .flatten()
+        .collect::<Vec<(usize, isize)>>();
+    events.sort();
+    let mut runs: Vec<(SectionSet, usize)> = Vec::new();
+    let mut count: isize = 0;
+    for (idx, &(position, delta)) in events.iter().enumerate() {
+        count += delta;
+        let end = match events.get(idx + 1) {
+            Some(&(next, _)) if next == position => continue,
+            Some(&(next, _)) => next - 1,
+            None if count > 0 => usize::MAX,
+            None => break,
+        };
+        match runs.last_mut() {
+            Some((run, run_count)) if *run_count == count as usize => run.end = end,
+            _ => runs.push((
+                SectionSet {
+                    start: position,
+                    end,
+                },
+                count as usize,
+            )),
+        }
+    }
+    runs
+}
+
+/// Camp-wide view of the assignments of all pairs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CampReport {
+    /// Runs of sections assigned to the same number of elves.
+    pub coverage: Vec<(SectionSet, usize)>,
+    /// Most elves any section is assigned to.
+    pub max_crowding: usize,
+    /// Sections assigned to `max_crowding` elves.
+    pub most_crowded: SectionUnion,
+    /// Elves whose every section is also assigned to another elf. Each can
+    /// be left out on its own, not necessarily all of them together.
+    pub redundant: Vec<Elf>,
+}
+
+impl fmt::Display for CampReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves per section:")?;
+        for (run, count) in &self.coverage {
+            writeln!(f, "  {}: {}", run, count)?;
+        }
+        writeln!(
+            f,
+            "most crowded: {} elves on {}",
+            self.max_crowding, self.most_crowded
+        )?;
+        writeln!(f, "redundant elves: {}", self.redundant.len())?;
+        for elf in &self.redundant {
+            writeln!(f, "  {}", elf)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sweeps over the assignments of all elves at once, in O(n log n).
+pub fn analyse_camp(assignments: &[Sections]) -> CampReport {
+    let elves = elves(assignments);
+    let coverage = coverage(&elves);
+    let max_crowding = coverage.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let runs_with = |wanted: usize| {
+        coverage
+            .iter()
+            .filter(|(_, count)| *count == wanted)
+            .map(|(run, _)| *run)
+            .collect::<SectionUnion>()
+    };
+    let most_crowded = runs_with(max_crowding);
+    // An elf is needed exactly when one of its sections is assigned to it alone
+    let assigned_once = runs_with(1);
+    let redundant = elves
+        .into_iter()
+        .filter(|elf| !assigned_once.intersects(&elf.sections))
+        .collect();
+    CampReport {
+        coverage,
+        max_crowding,
+        most_crowded,
+        redundant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{analyse_camp, coverage, elves, read_assignments};
+
+    #[test]
+    fn test_coverage() {
+        let input = read_assignments("2-3,6-7\n3-3,5-5\n").unwrap();
+        let runs = coverage(&elves(&input))
+            .iter()
+            .map(|(run, count)| format!("{}:{}", run, count))
+            .collect::<Vec<String>>();
+        assert_eq!(runs, vec!["2-2:1", "3-3:2", "4-4:0", "5-7:1"]);
+        assert!(coverage(&[]).is_empty());
+
+        let input = read_assignments(&format!("1-{},2-3\n", usize::MAX)).unwrap();
+        let runs = coverage(&elves(&input))
+            .iter()
+            .map(|(run, count)| format!("{}:{}", run, count))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            runs,
+            vec![
+                "1-1:1".to_string(),
+                "2-3:2".to_string(),
+                format!("4-{}:1", usize::MAX)
+            ]
+        );
+        assert_eq!(analyse_camp(&input).redundant.len(), 1);
+    }
+
+    #[test]
+    fn test_analyse_camp() {
+        let input = read_assignments(&read_to_string("input/example").unwrap()).unwrap();
+        let report = analyse_camp(&input);
+        let runs = report
+            .coverage
+            .iter()
+            .map(|(run, count)| format!("{}:{}", run, count))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            runs,
+            vec!["2-2:4", "3-3:5", "4-5:7", "6-6:8", "7-7:6", "8-8:4", "9-9:1"]
+        );
+        assert_eq!(report.max_crowding, 8);
+        assert_eq!(report.most_crowded.to_string(), "6-6");
+        assert_eq!(report.redundant.len(), 11);
+        assert!(report
+            .redundant
+            .iter()
+            .all(|elf| elf.to_string() != "pair 3 elf 2 (7-9)"));
+    }
+}
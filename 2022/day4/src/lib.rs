@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+use common::{Answer, Solution};
+
+mod camp;
+mod sections;
+
+pub use camp::{analyse_camp, coverage, elves, CampReport, Elf};
+pub use sections::{SectionSet, SectionUnion};
+
+pub type Sections = (SectionSet, SectionSet);
+
+fn get_owned_element<T>(coll: &mut Vec<T>, idx: usize) -> Option<T> {
+    if coll.get(idx).is_some() {
+        Some(coll.swap_remove(idx))
+    } else {
+        None
+    }
+}
+
+pub fn read_assignments(input: &str) -> Result<Vec<Sections>> {
+    let res = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut sections = line
+                .split(',')
+                .map(SectionSet::from_str)
+                .collect::<Result<Vec<SectionSet>>>()?;
+            Ok((
+                get_owned_element(&mut sections, 0)
+                    .ok_or_else(|| anyhow!("Could not get first section {:#?}", sections))?,
+                get_owned_element(&mut sections, 0)
+                    .ok_or_else(|| anyhow!("Could not get second section {:#?}", sections))?,
+            ))
+        })
+        .collect::<Result<Vec<Sections>>>()?;
+    Ok(res)
+}
+
+fn calculate_fully_overlapping(assignments: &[Sections]) -> usize {
+    assignments.iter().fold(0, |num, (el1, el2)| {
+        if el1.fully_overlaps(el2) || el2.fully_overlaps(el1) {
+            num + 1
+        } else {
+            num
+        }
+    })
+}
+
+fn calculate_partially_overlapping(assignments: &[Sections]) -> usize {
+    assignments.iter().fold(0, |num, (el1, el2)| {
+        if el1.partially_overlaps(el2) || el2.partially_overlaps(el1) {
+            num + 1
+        } else {
+            num
+        }
+    })
+}
+
+/// All sections assigned to an elf of any pair.
+pub fn assigned_sections(assignments: &[Sections]) -> SectionUnion {
+    assignments
+        .iter()
+        .flat_map(|(el1, el2)| [*el1, *el2])
+        .collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<Sections>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_assignments(input)
+    }
+
+    fn part1(assignments: &Self::Input) -> Result<Answer> {
+        Ok(calculate_fully_overlapping(assignments).into())
+    }
+
+    fn part2(assignments: &Self::Input) -> Result<Answer> {
+        Ok(calculate_partially_overlapping(assignments).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{
+        assigned_sections, calculate_fully_overlapping, calculate_partially_overlapping,
+        read_assignments,
+    };
+
+    #[test]
+    fn test_part1() {
+        let input = read_assignments(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(calculate_fully_overlapping(&input), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_assignments(&read_to_string("input/example").unwrap()).unwrap();
+        assert_eq!(calculate_partially_overlapping(&input), 4);
+    }
+
+    #[test]
+    fn test_assigned_sections() {
+        let input = read_assignments(&read_to_string("input/example").unwrap()).unwrap();
+        let assigned = assigned_sections(&input);
+        assert_eq!(assigned.to_string(), "2-9");
+        assert!(assigned.gaps().is_empty());
+        let input = read_assignments("1-2,8-9\n4-4,2-2\n").unwrap();
+        assert_eq!(assigned_sections(&input).gaps().to_string(), "3-3,5-7");
+    }
+}
@@ -0,0 +1,286 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+
+/// The sections from `start` to `end`, both included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SectionSet {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl FromStr for SectionSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ends = s.split('-').collect::<Vec<&str>>();
+        let (start, end): (&str, &str) = (
+            ends.first()
+                .ok_or_else(|| anyhow!("Could not get start {}", s))?,
+            ends.get(1)
+                .ok_or_else(|| anyhow!("Could not get end {}", s))?,
+        );
+        Self::new(start.parse()?, end.parse()?)
+    }
+}
+
+impl fmt::Display for SectionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl SectionSet {
+    pub fn new(start: usize, end: usize) -> Result<Self> {
+        if start > end {
+            return Err(anyhow!("Section {} comes after section {}", start, end));
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Number of sections in the set, never 0, saturating at `usize::MAX`.
+    pub fn num_sections(&self) -> usize {
+        (self.end - self.start).saturating_add(1)
+    }
+
+    pub fn contains(&self, section: usize) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether all sections of `self` are in `other`.
+    pub fn fully_overlaps(&self, other: &Self) -> bool {
+        self.start >= other.start && self.end <= other.end
+    }
+
+    /// Whether `self` and `other` share a section.
+    pub fn partially_overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end)).ok()
+    }
+
+    pub fn union(&self, other: &Self) -> SectionUnion {
+        [*self, *other].into_iter().collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> SectionUnion {
+        SectionUnion::from(*self).difference(&SectionUnion::from(*other))
+    }
+}
+
+/// Any sections, kept as sorted section sets that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SectionUnion {
+    sets: Vec<SectionSet>,
+}
+
+impl From<SectionSet> for SectionUnion {
+    fn from(set: SectionSet) -> Self {
+        Self { sets: vec![set] }
+    }
+}
+
+impl FromIterator<SectionSet> for SectionUnion {
+    /// Merges section sets that overlap or touch.
+    fn from_iter<I: IntoIterator<Item = SectionSet>>(iter: I) -> Self {
+        let mut sets = iter.into_iter().collect::<Vec<SectionSet>>();
+        sets.sort();
+        let mut merged: Vec<SectionSet> = Vec::with_capacity(sets.len());
+        for set in sets {
+            match merged.last_mut() {
+                Some(last) if set.start.saturating_sub(1) <= last.end => {
+                    last.end = last.end.max(set.end)
+                }
+                _ => merged.push(set),
+            }
+        }
+        Self { sets: merged }
+    }
+}
+
+impl fmt::Display for SectionUnion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sets = self
+            .sets
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        write!(f, "{}", sets.join(","))
+    }
+}
+
+impl SectionUnion {
+    /// The disjoint section sets, lowest sections first.
+    pub fn sets(&self) -> &[SectionSet] {
+        &self.sets
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    pub fn num_sections(&self) -> usize {
+        self.sets
+            .iter()
+            .map(SectionSet::num_sections)
+            .fold(0, usize::saturating_add)
+    }
+
+    pub fn contains(&self, section: usize) -> bool {
+        let idx = self.sets.partition_point(|set| set.end < section);
+        self.sets.get(idx).is_some_and(|set| set.contains(section))
+    }
+
+    /// Whether any section of `set` is in the union.
+    pub fn intersects(&self, set: &SectionSet) -> bool {
+        let idx = self.sets.partition_point(|other| other.end < set.start);
+        self.sets
+            .get(idx)
+            .is_some_and(|other| other.start <= set.end)
+    }
+
+    /// The smallest section set holding all sections, if any.
+    pub fn span(&self) -> Option<SectionSet> {
+        let (first, last) = (self.sets.first()?, self.sets.last()?);
+        Some(SectionSet {
+            start: first.start,
+            end: last.end,
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.sets.iter().chain(&other.sets).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut sets = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.sets.get(i), other.sets.get(j)) {
+            sets.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { sets }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut sets = Vec::new();
+        let mut j = 0;
+        for set in &self.sets {
+            while other.sets.get(j).is_some_and(|cut| cut.end < set.start) {
+                j += 1;
+            }
+            // First section not cut yet, none once a cut reaches usize::MAX
+            let mut start = Some(set.start);
+            for cut in other.sets[j..]
+                .iter()
+                .take_while(|cut| cut.start <= set.end)
+            {
+                let Some(from) = start else {
+                    break;
+                };
+                if cut.start > from {
+                    sets.push(SectionSet {
+                        start: from,
+                        end: cut.start - 1,
+                    });
+                }
+                start = cut.end.checked_add(1).map(|next| next.max(from));
+            }
+            if let Some(start) = start.filter(|start| *start <= set.end) {
+                sets.push(SectionSet {
+                    start,
+                    end: set.end,
+                });
+            }
+        }
+        Self { sets }
+    }
+
+    /// The sections between the lowest and the highest that are missing.
+    pub fn gaps(&self) -> Self {
+        match self.span() {
+            Some(span) => Self::from(span).difference(self),
+            None => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SectionSet, SectionUnion};
+
+    fn set(s: &str) -> SectionSet {
+        s.parse().unwrap()
+    }
+
+    fn union(s: &str) -> SectionUnion {
+        s.split(',').map(set).collect()
+    }
+
+    #[test]
+    fn test_section_set() {
+        assert!("5-4".parse::<SectionSet>().is_err());
+        assert_eq!(set("2-8").num_sections(), 7);
+        assert!(set("2-8").contains(8) && !set("2-8").contains(9));
+        assert_eq!(set("2-8").intersection(&set("6-9")), Some(set("6-8")));
+        assert_eq!(set("2-4").intersection(&set("6-9")), None);
+        assert_eq!(set("2-4").union(&set("5-9")).to_string(), "2-9");
+        assert_eq!(set("2-4").union(&set("6-9")).to_string(), "2-4,6-9");
+        assert_eq!(set("2-9").difference(&set("4-5")).to_string(), "2-3,6-9");
+        assert_eq!(set("2-9").difference(&set("1-9")).to_string(), "");
+    }
+
+    #[test]
+    fn test_section_union() {
+        let a = union("1-3,8-9,5-6,2-4");
+        assert_eq!(a.to_string(), "1-6,8-9");
+        assert_eq!(a.num_sections(), 8);
+        assert!(a.contains(6) && !a.contains(7) && a.contains(9));
+        assert!(a.intersects(&set("7-8")) && !a.intersects(&set("7-7")));
+        assert_eq!(a.gaps().to_string(), "7-7");
+        let b = union("3-8,12-12");
+        assert_eq!(a.union(&b).to_string(), "1-9,12-12");
+        assert_eq!(a.intersection(&b).to_string(), "3-6,8-8");
+        assert_eq!(a.difference(&b).to_string(), "1-2,9-9");
+        assert_eq!(b.difference(&a).to_string(), "7-7,12-12");
+        assert!(SectionUnion::default().gaps().is_empty());
+    }
+
+    #[test]
+    fn test_last_section() {
+        let max = usize::MAX;
+        let all = SectionSet::new(0, max).unwrap();
+        assert_eq!(all.num_sections(), max);
+        let top = SectionSet::new(max - 1, max).unwrap();
+        let a = union(&format!("1-3,{}-{}", max - 4, max - 2));
+        assert_eq!(
+            a.union(&top.into()).to_string(),
+            format!("1-3,{}-{}", max - 4, max)
+        );
+        assert_eq!(all.difference(&top).to_string(), format!("0-{}", max - 2));
+        assert_eq!(top.difference(&all).to_string(), "");
+        assert_eq!(
+            SectionUnion::from(all).difference(&a).to_string(),
+            format!("0-0,4-{},{}-{}", max - 5, max - 1, max)
+        );
+        assert_eq!(
+            a.union(&top.into()).gaps().to_string(),
+            format!("4-{}", max - 5)
+        );
+        assert!(a.union(&top.into()).contains(max));
+    }
+}
@@ -0,0 +1,46 @@
+//! Compares moving crates on plain `Vec` stacks with `RopeStacks` on large
+//! generated inputs. Run with `cargo bench -p day5`.
+
+use anyhow::Result;
+
+use common::measure;
+use day5::{
+    average_move, move_crates_rope, move_crates_vec, Crane, CrateMover9000, CrateMover9001,
+    Generator,
+};
+
+const WARMUP: usize = 1;
+const ITERATIONS: usize = 5;
+
+fn main() -> Result<()> {
+    let cranes: [(&dyn Crane, bool); 2] = [(&CrateMover9000, true), (&CrateMover9001, false)];
+    for (max_height, moves) in [
+        (100, 100_000),
+        (1_000, 100_000),
+        (10_000, 10_000),
+        (100_000, 10_000),
+    ] {
+        let input = Generator {
+            max_height,
+            moves,
+            ..Generator::default()
+        }
+        .generate()?;
+        for (crane, reverse) in cranes {
+            let name = format!(
+                "height {} moves {} (average {} crates) crane {}",
+                max_height,
+                moves,
+                average_move(&input),
+                crane.name()
+            );
+            let vec = measure(WARMUP, ITERATIONS, || Ok(move_crates_vec(&input, crane)?))?;
+            println!("{} vec: {}", name, vec);
+            let rope = measure(WARMUP, ITERATIONS, || {
+                Ok(move_crates_rope(&input, reverse)?)
+            })?;
+            println!("{} rope: {}", name, rope);
+        }
+    }
+    Ok(())
+}
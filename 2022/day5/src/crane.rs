@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+
+use common::Part;
+
+/// A model of crane, deciding how the crates of a move land on the target
+/// stack.
+pub trait Crane {
+    /// Name selecting the crane on the command line, see [`parse_crane`].
+    fn name(&self) -> String;
+
+    /// Orders `crates`, the top crates of the origin stack listed bottom
+    /// first, the way they end up on the target stack, bottom first.
+    fn arrange(&self, crates: Vec<char>) -> Vec<char>;
+
+    /// Whether the crane moves all crates of a move as one block, reversed
+    /// or not, so they can be moved in bulk.
+    fn reverses(&self) -> Option<bool> {
+        None
+    }
+}
+
+/// Moves one crate at a time, reversing their order.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "9000".to_string()
+    }
+
+    fn arrange(&self, mut crates: Vec<char>) -> Vec<char> {
+        crates.reverse();
+        crates
+    }
+
+    fn reverses(&self) -> Option<bool> {
+        Some(true)
+    }
+}
+
+/// Moves all crates at once, keeping their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "9001".to_string()
+    }
+
+    fn arrange(&self, crates: Vec<char>) -> Vec<char> {
+        crates
+    }
+
+    fn reverses(&self) -> Option<bool> {
+        Some(false)
+    }
+}
+
+/// Moves at most `capacity` crates at once, keeping the order within each
+/// batch.
+pub struct CapacityCrane {
+    pub capacity: usize,
+}
+
+impl Crane for CapacityCrane {
+    fn name(&self) -> String {
+        format!("capacity:{}", self.capacity)
+    }
+
+    fn arrange(&self, crates: Vec<char>) -> Vec<char> {
+        crates.rchunks(self.capacity).flatten().copied().collect()
+    }
+}
+
+/// Moves batches of at most `capacity` crates onto a holding buffer, then
+/// unloads the buffer one crate at a time.
+pub struct BufferCrane {
+    pub capacity: usize,
+}
+
+impl Crane for BufferCrane {
+    fn name(&self) -> String {
+        format!("buffer:{}", self.capacity)
+    }
+
+    fn arrange(&self, crates: Vec<char>) -> Vec<char> {
+        let mut buffer = CapacityCrane {
+            capacity: self.capacity,
+        }
+        .arrange(crates);
+        buffer.reverse();
+        buffer
+    }
+}
+
+/// The crane of the puzzle part.
+pub fn part_crane(part: Part) -> &'static dyn Crane {
+    match part {
+        Part::Part1 => &CrateMover9000,
+        Part::Part2 => &CrateMover9001,
+    }
+}
+
+/// Parses a crane name: `9000`, `9001`, `capacity:N` or `buffer:N`.
+pub fn parse_crane(name: &str) -> Result<Box<dyn Crane>> {
+    let capacity = |value: &str| match value.parse::<usize>() {
+        Ok(capacity) if capacity > 0 => Ok(capacity),
+        _ => Err(anyhow!("Invalid crane capacity {}", value)),
+    };
+    match name.split_once(':') {
+        None if name == "9000" => Ok(Box::new(CrateMover9000)),
+        None if name == "9001" => Ok(Box::new(CrateMover9001)),
+        Some(("capacity", value)) => Ok(Box::new(CapacityCrane {
+            capacity: capacity(value)?,
+        })),
+        Some(("buffer", value)) => Ok(Box::new(BufferCrane {
+            capacity: capacity(value)?,
+        })),
+        _ => Err(anyhow!("Unknown crane {}", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{get_top_crates, move_crates, parse_crane, read_input};
+
+    fn arrange(name: &str, crates: &str) -> String {
+        let crane = parse_crane(name).unwrap();
+        assert_eq!(crane.name(), name);
+        crane
+            .arrange(crates.chars().collect())
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_arrange() {
+        assert_eq!(arrange("9000", "ABCDE"), "EDCBA");
+        assert_eq!(arrange("9001", "ABCDE"), "ABCDE");
+        assert_eq!(arrange("capacity:2", "ABCDE"), "DEBCA");
+        assert_eq!(arrange("buffer:2", "ABCDE"), "ACBED");
+        assert!(parse_crane("capacity:0").is_err());
+        assert!(parse_crane("9002").is_err());
+    }
+
+    #[test]
+    fn test_cranes() {
+        let input = read_input(&read_to_string("input/example").unwrap()).unwrap();
+        let top_crates =
+            |name| get_top_crates(&move_crates(&input, &*parse_crane(name).unwrap()).unwrap());
+        assert_eq!(top_crates("capacity:1"), "CMZ");
+        assert_eq!(top_crates("capacity:3"), "MCD");
+        assert_eq!(top_crates("buffer:3"), "CMZ");
+    }
+}
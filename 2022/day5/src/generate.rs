@@ -0,0 +1,111 @@
+use anyhow::{anyhow, Result};
+use rand::{rngs::StdRng, RngExt, SeedableRng};
+
+use crate::{Input, Move, Stacks};
+
+/// Shape of the random inputs made by [`Generator::generate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Generator {
+    pub stacks: usize,
+    /// Highest stack at the start; each stack holds between 0 and this many
+    /// crates.
+    pub max_height: usize,
+    pub moves: usize,
+    /// The same seed always generates the same input.
+    pub seed: u64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            stacks: 9,
+            max_height: 8,
+            moves: 500,
+            seed: 0,
+        }
+    }
+}
+
+impl Generator {
+    /// Generates stacks of random crates and moves that are all valid, with
+    /// any crane.
+    pub fn generate(&self) -> Result<Input> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let stacks: Stacks = (0..self.stacks)
+            .map(|_| {
+                let height = rng.random_range(0..=self.max_height);
+                (0..height).map(|_| rng.random_range('A'..='Z')).collect()
+            })
+            .collect();
+        // Where the crates are only depends on the moves, not on the crane
+        let mut heights = stacks.iter().map(Vec::len).collect::<Vec<usize>>();
+        let moves = (0..self.moves)
+            .map(|_| {
+                let origins = (0..self.stacks)
+                    .filter(|&idx| heights[idx] > 0)
+                    .collect::<Vec<usize>>();
+                if origins.is_empty() {
+                    return Err(anyhow!("There are no crates to move"));
+                }
+                let from = origins[rng.random_range(0..origins.len())];
+                let to = match self.stacks {
+                    1 => from,
+                    _ => (from + rng.random_range(1..self.stacks)) % self.stacks,
+                };
+                let num = rng.random_range(1..=heights[from]);
+                heights[from] -= num;
+                heights[to] += num;
+                Ok(Move {
+                    num,
+                    from: from + 1,
+                    to: to + 1,
+                    line: None,
+                })
+            })
+            .collect::<Result<Vec<Move>>>()?;
+        Ok((stacks, moves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{move_crates, read_input, write_input, CrateMover9000, CrateMover9001, Generator};
+
+    #[test]
+    fn test_generate() {
+        let generator = Generator {
+            stacks: 5,
+            max_height: 10,
+            moves: 200,
+            seed: 42,
+        };
+        let input = generator.generate().unwrap();
+        assert_eq!(input.0.len(), 5);
+        assert!(input.0.iter().all(|stack| stack.len() <= 10));
+        assert_eq!(input.1.len(), 200);
+        assert_eq!(generator.generate().unwrap(), input);
+        let other = Generator {
+            seed: 43,
+            ..generator
+        };
+        assert_ne!(other.generate().unwrap(), input);
+
+        let input = read_input(&write_input(&input)).unwrap();
+        assert!(move_crates(&input, &CrateMover9000).is_ok());
+        assert!(move_crates(&input, &CrateMover9001).is_ok());
+    }
+
+    #[test]
+    fn test_no_crates() {
+        let generator = Generator {
+            max_height: 0,
+            ..Generator::default()
+        };
+        assert!(generator.generate().is_err());
+        let generator = Generator {
+            moves: 0,
+            ..generator
+        };
+        assert!(generator.generate().is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use lazy_static::lazy_static;
+use regex::{Match, Regex};
+
+use common::{Answer, Part, Solution};
+
+mod crane;
+mod generate;
+mod render;
+mod rope;
+mod validate;
+
+pub use crane::{
+    parse_crane, part_crane, BufferCrane, CapacityCrane, Crane, CrateMover9000, CrateMover9001,
+};
+pub use generate::Generator;
+pub use render::{draw_stacks, play, write_input, Playback};
+pub use rope::RopeStacks;
+use validate::check_heights;
+pub use validate::{check_move, MoveError, Validation};
+
+const MOVE_RE: &str = r"move (?P<num>\d+) from (?P<from>\d+) to (?P<to>\d+)";
+
+#[derive(Clone, Debug, Eq)]
+pub struct Move {
+    num: usize,
+    from: usize,
+    to: usize,
+    /// Line of the input the move was read from.
+    line: Option<usize>,
+}
+
+/// Moves are equal when they move the same crates, wherever they were read
+/// from.
+impl PartialEq for Move {
+    fn eq(&self, other: &Self) -> bool {
+        (self.num, self.from, self.to) == (other.num, other.from, other.to)
+    }
+}
+pub type Stacks = Vec<Vec<char>>;
+pub type Moves = Vec<Move>;
+pub type Input = (Stacks, Moves);
+
+fn usize_from_match(m: Option<Match>) -> Result<usize> {
+    m.ok_or_else(|| anyhow!("cannot read to"))?
+        .as_str()
+        .parse::<usize>()
+        .map_err(|e| anyhow!("Cannot parse number: {}", e))
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(MOVE_RE).unwrap();
+        }
+        if let Some(captures) = RE.captures(s) {
+            Ok(Move {
+                num: usize_from_match(captures.name("num"))?,
+                from: usize_from_match(captures.name("from"))?,
+                to: usize_from_match(captures.name("to"))?,
+                line: None,
+            })
+        } else {
+            Err(anyhow!("Could not parse move: {}", s))
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.from, self.to)
+    }
+}
+
+pub fn read_input(input: &str) -> Result<Input> {
+    let mut line_iter = input.lines().enumerate();
+    let mut footer = None;
+    let mut stacks: Stacks = line_iter
+        .by_ref()
+        .map(|(_, line)| line)
+        .take_while(|line| {
+            if line.contains('[') {
+                true
+            } else {
+                footer = Some(*line);
+                false
+            }
+        })
+        .try_fold(Stacks::new(), |mut columns, line| {
+            let line = line.chars().collect::<Vec<char>>().chunks(4).try_fold(
+                Vec::<Option<char>>::new(),
+                |mut row, chunk| {
+                    let c = chunk
+                        .get(1)
+                        .ok_or_else(|| anyhow!("Could not get crate contents"))?
+                        .to_owned();
+                    if c != ' ' {
+                        row.push(Some(c));
+                    } else {
+                        row.push(None);
+                    }
+                    Ok::<Vec<Option<char>>, anyhow::Error>(row)
+                },
+            )?;
+            columns.resize(line.len(), Vec::new());
+            for (idx, maybe_crate) in line.into_iter().enumerate() {
+                if let Some(crate_) = maybe_crate {
+                    columns.get_mut(idx).unwrap().push(crate_);
+                }
+            }
+            Ok::<Stacks, anyhow::Error>(columns)
+        })?;
+    // The footer numbers all stacks, including those that start out empty
+    let num_stacks = footer.map_or(0, |line| line.split_whitespace().count());
+    if stacks.len() < num_stacks {
+        stacks.resize(num_stacks, Vec::new());
+    }
+    let stacks = stacks
+        .into_iter()
+        .map(|s| s.into_iter().rev().collect())
+        .collect();
+    let moves = line_iter
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| {
+            let mov = Move::from_str(line).map_err(|e| anyhow!("Line {}: {}", idx + 1, e))?;
+            Ok(Move {
+                line: Some(idx + 1),
+                ..mov
+            })
+        })
+        .collect::<Result<Moves>>()?;
+    Ok((stacks, moves))
+}
+
+/// Carries out `mov`, which must have passed [`check_move`].
+fn apply_move(stacks: &mut Stacks, mov: &Move, crane: &dyn Crane) {
+    let origin_stack = &mut stacks[mov.from - 1];
+    let stack = origin_stack.drain((origin_stack.len() - mov.num)..);
+    let mut crates = crane.arrange(stack.collect());
+    stacks[mov.to - 1].append(&mut crates);
+}
+
+/// The outcome of running moves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Executed {
+    pub stacks: Stacks,
+    /// Invalid moves skipped with [`Validation::Lenient`].
+    pub skipped: Vec<MoveError>,
+}
+
+/// Runs the moves of `input` with `crane`, calling `on_move` with the
+/// stacks after each move carried out. Fails with a [`MoveError`] on an
+/// invalid move unless `validation` is lenient.
+pub fn run_moves(
+    input: &Input,
+    crane: &dyn Crane,
+    validation: Validation,
+    mut on_move: impl FnMut(&Move, &Stacks) -> Result<()>,
+) -> Result<Executed> {
+    let mut stacks = input.0.clone();
+    let mut skipped = Vec::new();
+    for (index, mov) in input.1.iter().enumerate() {
+        match check_move(&stacks, mov, index) {
+            Ok(()) => {
+                apply_move(&mut stacks, mov, crane);
+                on_move(mov, &stacks)?;
+            }
+            Err(e) if validation == Validation::Lenient => skipped.push(e),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(Executed { stacks, skipped })
+}
+
+/// Average number of crates per move above which moving them in bulk on
+/// [`RopeStacks`] beats copying them, see `benches/stacks.rs`.
+const BULK_MOVE_CRATES: usize = 4096;
+
+/// Average number of crates the moves of `input` take.
+pub fn average_move(input: &Input) -> usize {
+    let total = input
+        .1
+        .iter()
+        .map(|mov| mov.num)
+        .fold(0, usize::saturating_add);
+    total / input.1.len().max(1)
+}
+
+/// Runs the moves of `input` with `crane`, in bulk if the crane allows and
+/// the moves are large.
+pub fn move_crates(input: &Input, crane: &dyn Crane) -> Result<Stacks, MoveError> {
+    match crane.reverses() {
+        Some(reverse) if average_move(input) > BULK_MOVE_CRATES => move_crates_rope(input, reverse),
+        _ => move_crates_vec(input, crane),
+    }
+}
+
+/// Runs the moves of `input` on [`Stacks`], copying the crates of each move.
+pub fn move_crates_vec(input: &Input, crane: &dyn Crane) -> Result<Stacks, MoveError> {
+    input
+        .1
+        .iter()
+        .enumerate()
+        .try_fold(input.0.clone(), |mut stacks, (index, mov)| {
+            check_move(&stacks, mov, index)?;
+            apply_move(&mut stacks, mov, crane);
+            Ok(stacks)
+        })
+}
+
+/// Runs the moves of `input` on [`RopeStacks`], moving the crates of each
+/// move as one block, reversed if `reverse`.
+pub fn move_crates_rope(input: &Input, reverse: bool) -> Result<Stacks, MoveError> {
+    let mut stacks = RopeStacks::new(&input.0);
+    for (index, mov) in input.1.iter().enumerate() {
+        check_heights(stacks.num_stacks(), |idx| stacks.height(idx), mov, index)?;
+        stacks.move_crates(mov.num, mov.from - 1, mov.to - 1, reverse);
+    }
+    Ok(stacks.to_stacks())
+}
+
+pub fn get_top_crates(stacks: &Stacks) -> String {
+    stacks.iter().filter_map(|s| s.last()).collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        read_input(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(get_top_crates(&move_crates(input, part_crane(Part::Part1))?).into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(get_top_crates(&move_crates(input, part_crane(Part::Part2))?).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{get_top_crates, move_crates, part_crane, read_input};
+    use common::Part;
+
+    #[test]
+    fn test_part1() {
+        let input = read_input(&read_to_string("input/example").unwrap()).unwrap();
+        let top_crates = get_top_crates(&move_crates(&input, part_crane(Part::Part1)).unwrap());
+        assert_eq!(top_crates, "CMZ")
+    }
+}
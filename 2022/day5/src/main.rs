@@ -1,159 +1,122 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    str::FromStr,
-};
-
-use anyhow::{anyhow, Error, Result};
-use lazy_static::lazy_static;
-use regex::{Match, Regex};
+use std::time::Duration;
 
-use common::{get_filename, Part};
+use anyhow::{anyhow, Error};
+use clap::{Args, Parser};
 
-const MOVE_RE: &str = r"move (?P<num>\d+) from (?P<from>\d+) to (?P<to>\d+)";
+use common::{run_entry, Entry, InputSource, Solution};
+use day5::{
+    get_top_crates, parse_crane, part_crane, play, read_input, run_moves, write_input, Crane, Day5,
+    Generator, Playback, Validation,
+};
 
-#[derive(Clone, Debug)]
-struct Move {
-    num: usize,
-    from: usize,
-    to: usize,
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// Print a random input instead of solving one
+    #[arg(long)]
+    generate: bool,
+    /// Stacks of the generated input
+    #[arg(long, default_value_t = 9, requires = "generate")]
+    stacks: usize,
+    /// Most crates a stack of the generated input starts with
+    #[arg(long, default_value_t = 8, requires = "generate")]
+    max_height: usize,
+    /// Moves of the generated input
+    #[arg(long, default_value_t = 500, requires = "generate")]
+    moves: usize,
+    /// Seed of the generated input
+    #[arg(long, default_value_t = 0, requires = "generate")]
+    seed: u64,
 }
-type Stacks = Vec<Vec<char>>;
-type Moves = Vec<Move>;
-type Input = (Stacks, Moves);
 
-fn usize_from_match(m: Option<Match>) -> Result<usize> {
-    m.ok_or_else(|| anyhow!("cannot read to"))?
-        .as_str()
-        .parse::<usize>()
-        .map_err(|e| anyhow!("Cannot parse number: {}", e))
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Day5Args {
+    /// Crane to run the moves with instead of those of the selected parts:
+    /// 9000, 9001, capacity:N or buffer:N. Can be given more than once to
+    /// compare cranes
+    #[arg(long)]
+    crane: Vec<String>,
+    /// Draw the stacks after every move
+    #[arg(long, conflicts_with = "step")]
+    animate: bool,
+    /// Time each drawing of --animate stays on screen
+    #[arg(long, default_value_t = 500, requires = "animate")]
+    delay_ms: u64,
+    /// Draw the stacks after every move and wait for enter
+    #[arg(long)]
+    step: bool,
+    /// Skip moves that cannot be carried out instead of failing
+    #[arg(long)]
+    lenient: bool,
+    #[command(flatten)]
+    generate: GenerateArgs,
+    #[command(flatten)]
+    run: common::Args,
 }
 
-impl FromStr for Move {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(MOVE_RE).unwrap();
-        }
-        if let Some(captures) = RE.captures(s) {
-            Ok(Move {
-                num: usize_from_match(captures.name("num"))?,
-                from: usize_from_match(captures.name("from"))?,
-                to: usize_from_match(captures.name("to"))?,
-            })
-        } else {
-            Err(anyhow!("Could not parse move: {}", s))
-        }
+fn main() -> Result<(), Error> {
+    let args = Day5Args::parse();
+    if args.generate.generate {
+        let generator = Generator {
+            stacks: args.generate.stacks,
+            max_height: args.generate.max_height,
+            moves: args.generate.moves,
+            seed: args.generate.seed,
+        };
+        print!("{}", write_input(&generator.generate()?));
+        return Ok(());
     }
-}
-
-fn read_file(filename: &str) -> Result<Input> {
-    let file = File::open(filename)?;
-    let mut line_iter = BufReader::new(&file).lines();
-    //.filter(|el| if let Ok(x) = el { !x.is_empty() } else { false })
-    let stacks: Stacks = line_iter
-        .by_ref()
-        .take_while(|line| {
-            if let Ok(l) = line {
-                l.contains('[')
-            } else {
-                false
-            }
-        })
-        .fold(Ok(Stacks::new()), |mut columns: Result<Stacks>, line| {
-            let line = line?.chars().collect::<Vec<char>>().chunks(4).fold(
-                Ok::<Vec<Option<char>>, anyhow::Error>(Vec::<Option<char>>::new()),
-                |mut row, chunk| {
-                    let c = chunk
-                        .get(1)
-                        .ok_or_else(|| anyhow!("Could not get crate contents"))?
-                        .to_owned();
-                    if c != ' ' {
-                        row.as_mut()
-                            .map(|r| r.push(Some(c)))
-                            .map_err(|e| anyhow!("{}", e))?;
-                    } else {
-                        row.as_mut()
-                            .map(|r| r.push(None))
-                            .map_err(|e| anyhow!("{}", e))?;
-                    }
-                    row
-                },
-            )?;
-            if let Ok(ref mut cols) = columns {
-                cols.resize(line.len(), Vec::new());
-            }
-            for (idx, maybe_crate) in line.into_iter().enumerate() {
-                if let Some(crate_) = maybe_crate {
-                    if let Ok(ref mut cols) = columns {
-                        cols.get_mut(idx).unwrap().push(crate_);
-                    }
-                }
-            }
-            columns
-        })?;
-    let stacks = stacks
-        .into_iter()
-        .map(|s| s.into_iter().rev().collect())
-        .collect();
-    let moves = line_iter
-        .filter(|line| line.as_ref().map(|l| !l.is_empty()).unwrap_or(false))
-        .map(|el| Move::from_str(&el?))
-        .collect::<Result<Moves>>()?;
-    Ok((stacks, moves))
-}
-
-fn move_crates(input: Input, part: Part) -> Result<Stacks> {
-    input
-        .1
+    let playback = if args.animate {
+        Some(Playback::Animate(Duration::from_millis(args.delay_ms)))
+    } else if args.step {
+        Some(Playback::Step)
+    } else {
+        None
+    };
+    if playback.is_none() && args.crane.is_empty() && !args.lenient {
+        return run_entry(Entry::of::<Day5>(), &args.run);
+    }
+    let source = args.run.source();
+    if playback == Some(Playback::Step) && source == InputSource::Stdin {
+        return Err(anyhow!("--step reads enter from stdin, give an input file"));
+    }
+    let parsed = args
+        .crane
         .iter()
-        .fold(Ok(input.0), |mut stacks: Result<Stacks>, mov| {
-            if let Ok(ref mut s) = stacks {
-                let mut crates: Vec<char> = {
-                    let origin_stack = s
-                        .get_mut(mov.from - 1)
-                        .ok_or_else(|| anyhow!("Could not get origin stack"))?;
-                    let stack = origin_stack
-                        .drain((origin_stack.len() - mov.num)..);
-                    let stack = if part == Part::Part1 {
-                        stack.rev().collect()
-                    } else { stack.collect() };
-                    stack
-                };
-                let target_stack = s
-                    .get_mut(mov.to - 1)
-                    .ok_or_else(|| anyhow!("Could not get target stack"))?;
-                target_stack.append(&mut crates)
+        .map(|name| parse_crane(name))
+        .collect::<Result<Vec<Box<dyn Crane>>, Error>>()?;
+    let cranes: Vec<&dyn Crane> = if parsed.is_empty() {
+        args.run
+            .parts()
+            .parts()
+            .into_iter()
+            .map(part_crane)
+            .collect()
+    } else {
+        parsed.iter().map(|crane| &**crane).collect()
+    };
+    let validation = if args.lenient {
+        Validation::Lenient
+    } else {
+        Validation::Strict
+    };
+    let input = read_input(&source.read(Day5::DAY)?)?;
+    for crane in cranes {
+        let executed = match playback {
+            Some(playback) => play(&input, crane, validation, playback)?,
+            None => {
+                let executed = run_moves(&input, crane, validation, |_, _| Ok(()))?;
+                println!(
+                    "crane {}: {}",
+                    crane.name(),
+                    get_top_crates(&executed.stacks)
+                );
+                executed
             }
-            //println!("stacks: {:#?}", stacks);
-            stacks
-        })
-}
-
-fn get_top_crates(stacks: &Stacks) -> String {
-    stacks.iter().filter_map(|s| s.last()).collect()
-}
-
-fn main() -> Result<(), Error> {
-    let filename = get_filename();
-    let input = read_file(&filename)?;
-    let top_crates_part1 = get_top_crates(&move_crates(input.clone(), Part::Part1)?);
-    println!("part 1: {}", top_crates_part1);
-    let top_crates_part2 = get_top_crates(&move_crates(input, Part::Part2)?);
-    println!("part 2: {}", top_crates_part2);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{get_top_crates, move_crates, read_file};
-
-    #[test]
-    fn test_part1() {
-        let input = read_file("input/example").unwrap();
-        let top_crates = get_top_crates(&move_crates(input).unwrap());
-        assert_eq!(top_crates, "CMZ")
+        };
+        for error in executed.skipped {
+            eprintln!("crane {}: skipped {}", crane.name(), error);
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,160 @@
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use crate::{get_top_crates, run_moves, Crane, Executed, Input, Stacks, Validation};
+
+/// Clears the terminal and moves the cursor to its top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Draws `stacks` the way the puzzle input does, with the top crates first
+/// and a footer numbering the stacks, each number under its column.
+pub fn draw_stacks(stacks: &Stacks) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(crate_) => format!("[{}]", crate_),
+                None => "   ".to_string(),
+            })
+            .collect::<Vec<String>>();
+        drawing.push_str(&row.join(" "));
+        drawing.push('\n');
+    }
+    let footer = (1..=stacks.len())
+        .map(|idx| format!("{:^3}", idx))
+        .collect::<Vec<String>>();
+    drawing.push_str(&footer.join(" "));
+    drawing.push('\n');
+    drawing
+}
+
+/// Writes `input` back in the puzzle input format: the drawing of the
+/// stacks, a blank line and one move per line.
+pub fn write_input(input: &Input) -> String {
+    let mut text = draw_stacks(&input.0);
+    text.push('\n');
+    for mov in &input.1 {
+        text.push_str(&format!("{}\n", mov));
+    }
+    text
+}
+
+/// How [`play`] advances from one move to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Playback {
+    /// Waits this long after each move.
+    Animate(Duration),
+    /// Waits for enter to be pressed after each move.
+    Step,
+}
+
+fn show(title: &str, stacks: &Stacks) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    write!(
+        stdout,
+        "{}{}\n\n{}",
+        CLEAR_SCREEN,
+        title,
+        draw_stacks(stacks)
+    )?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn wait(playback: Playback) -> Result<()> {
+    match playback {
+        Playback::Animate(delay) => thread::sleep(delay),
+        Playback::Step => {
+            print!("\npress enter for the next move");
+            io::stdout().flush()?;
+            io::stdin().lock().read_line(&mut String::new())?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the moves of `input` with `crane`, drawing the stacks on the
+/// terminal before the first move and after each move.
+pub fn play(
+    input: &Input,
+    crane: &dyn Crane,
+    validation: Validation,
+    playback: Playback,
+) -> Result<Executed> {
+    let total = input.1.len();
+    let name = crane.name();
+    show(&format!("crane {}: start", name), &input.0)?;
+    wait(playback)?;
+    let mut done = 0;
+    let executed = run_moves(input, crane, validation, |mov, stacks| {
+        done += 1;
+        show(
+            &format!("crane {}: {} ({}/{})", name, mov, done, total),
+            stacks,
+        )?;
+        wait(playback)
+    })?;
+    println!("\ntop crates: {}", get_top_crates(&executed.stacks));
+    Ok(executed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{draw_stacks, read_input, write_input, Input, Move};
+
+    fn input_strategy() -> impl Strategy<Value = Input> {
+        (1..13usize).prop_flat_map(|num_stacks| {
+            let stacks = vec(vec(proptest::char::range('A', 'Z'), 0..8), num_stacks);
+            let mov =
+                (1..50usize, 1..=num_stacks, 1..=num_stacks).prop_map(|(num, from, to)| Move {
+                    num,
+                    from,
+                    to,
+                    line: None,
+                });
+            (stacks, vec(mov, 0..20))
+        })
+    }
+
+    #[test]
+    fn test_draw_stacks() {
+        let example = read_to_string("input/example").unwrap();
+        let (stacks, _) = read_input(&example).unwrap();
+        let drawing = example.split("\n\n").next().unwrap();
+        assert_eq!(draw_stacks(&stacks), format!("{}\n", drawing));
+    }
+
+    #[test]
+    fn test_write_input() {
+        let example = read_to_string("input/example").unwrap();
+        assert_eq!(write_input(&read_input(&example).unwrap()), example);
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        let stacks = (0..11).map(|idx| vec![(b'A' + idx) as char]).collect();
+        let drawing = draw_stacks(&stacks);
+        let (crates, footer) = drawing.trim_end().split_once('\n').unwrap();
+        assert_eq!(crates.find('K'), Some(41));
+        assert_eq!(footer.find("11"), Some(40));
+        assert_eq!(footer.find("10"), Some(36));
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(input in input_strategy()) {
+            prop_assert_eq!(read_input(&write_input(&input)).unwrap(), input);
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::Stacks;
+
+const NIL: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Node {
+    crate_: char,
+    priority: u64,
+    /// Number of crates in the subtree.
+    size: usize,
+    left: usize,
+    right: usize,
+    /// The subtree is to be read in reverse, not yet pushed to the children.
+    reversed: bool,
+}
+
+/// Stacks of crates kept as implicit treaps in one node arena. Splitting off
+/// the top crates of a stack, reversing them and joining them onto another
+/// stack all take O(log n), however many crates a move takes.
+#[derive(Clone, Debug)]
+pub struct RopeStacks {
+    nodes: Vec<Node>,
+    /// Root node of every stack, bottom crate first.
+    roots: Vec<usize>,
+    /// State of the xorshift generator for node priorities.
+    seed: u64,
+}
+
+impl RopeStacks {
+    pub fn new(stacks: &Stacks) -> Self {
+        let mut rope = Self {
+            nodes: Vec::with_capacity(stacks.iter().map(Vec::len).sum()),
+            roots: Vec::with_capacity(stacks.len()),
+            seed: 0x2545_f491_4f6c_dd1d,
+        };
+        for stack in stacks {
+            let root = rope.build(stack);
+            rope.roots.push(root);
+        }
+        rope
+    }
+
+    pub fn num_stacks(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Number of crates on stack `idx`, counting from 0.
+    pub fn height(&self, idx: usize) -> usize {
+        self.size(self.roots[idx])
+    }
+
+    /// Moves the top `num` crates of stack `from` onto stack `to`, counting
+    /// from 0, reversing their order if `reverse`.
+    pub fn move_crates(&mut self, num: usize, from: usize, to: usize, reverse: bool) {
+        let height = self.height(from);
+        let (rest, top) = self.split(self.roots[from], height - num);
+        self.roots[from] = rest;
+        if reverse && top != NIL {
+            self.nodes[top].reversed ^= true;
+        }
+        self.roots[to] = self.merge(self.roots[to], top);
+    }
+
+    pub fn to_stacks(&self) -> Stacks {
+        self.roots
+            .iter()
+            .map(|&root| {
+                let mut stack = Vec::with_capacity(self.size(root));
+                self.collect(root, false, &mut stack);
+                stack
+            })
+            .collect()
+    }
+
+    fn new_node(&mut self, crate_: char) -> usize {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.nodes.push(Node {
+            crate_,
+            priority: self.seed,
+            size: 1,
+            left: NIL,
+            right: NIL,
+            reversed: false,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Builds the tree of `stack` in linear time, keeping the nodes on its
+    /// right edge to find where each new crate goes.
+    fn build(&mut self, stack: &[char]) -> usize {
+        let mut right_edge: Vec<usize> = Vec::new();
+        for crate_ in stack {
+            let node = self.new_node(*crate_);
+            let mut left = NIL;
+            while let Some(&last) = right_edge.last() {
+                if self.nodes[last].priority > self.nodes[node].priority {
+                    break;
+                }
+                left = last;
+                right_edge.pop();
+            }
+            self.nodes[node].left = left;
+            if let Some(&parent) = right_edge.last() {
+                self.nodes[parent].right = node;
+            }
+            right_edge.push(node);
+        }
+        let root = right_edge.first().copied().unwrap_or(NIL);
+        self.update_sizes(root);
+        root
+    }
+
+    fn update_sizes(&mut self, node: usize) -> usize {
+        if node == NIL {
+            return 0;
+        }
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = self.update_sizes(left) + self.update_sizes(right) + 1;
+        self.nodes[node].size
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = self.size(left) + self.size(right) + 1;
+    }
+
+    /// Pushes a pending reversal of `node` down to its children.
+    fn push(&mut self, node: usize) {
+        if !self.nodes[node].reversed {
+            return;
+        }
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[node].left = right;
+        self.nodes[node].right = left;
+        self.nodes[node].reversed = false;
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].reversed ^= true;
+            }
+        }
+    }
+
+    /// Splits the tree at `node` into its first `k` crates and the rest.
+    fn split(&mut self, node: usize, k: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        self.push(node);
+        let left = self.nodes[node].left;
+        if self.size(left) >= k {
+            let (first, rest) = self.split(left, k);
+            self.nodes[node].left = rest;
+            self.update(node);
+            (first, node)
+        } else {
+            let right = self.nodes[node].right;
+            let (first, rest) = self.split(right, k - self.size(left) - 1);
+            self.nodes[node].right = first;
+            self.update(node);
+            (node, rest)
+        }
+    }
+
+    /// Joins the trees at `a` and `b`, the crates of `b` going on top.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            self.push(a);
+            let right = self.nodes[a].right;
+            self.nodes[a].right = self.merge(right, b);
+            self.update(a);
+            a
+        } else {
+            self.push(b);
+            let left = self.nodes[b].left;
+            self.nodes[b].left = self.merge(a, left);
+            self.update(b);
+            b
+        }
+    }
+
+    fn collect(&self, node: usize, reversed: bool, stack: &mut Vec<char>) {
+        if node == NIL {
+            return;
+        }
+        let Node {
+            crate_,
+            left,
+            right,
+            ..
+        } = self.nodes[node];
+        let reversed = reversed ^ self.nodes[node].reversed;
+        let (first, last) = if reversed {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        self.collect(first, reversed, stack);
+        stack.push(crate_);
+        self.collect(last, reversed, stack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        move_crates_rope, move_crates_vec, CrateMover9000, CrateMover9001, Generator, RopeStacks,
+    };
+
+    #[test]
+    fn test_rope_stacks() {
+        let stacks = vec![vec!['A', 'B', 'C', 'D'], vec![], vec!['E']];
+        let mut rope = RopeStacks::new(&stacks);
+        assert_eq!(rope.to_stacks(), stacks);
+        rope.move_crates(3, 0, 2, true);
+        assert_eq!(
+            rope.to_stacks(),
+            vec![vec!['A'], vec![], vec!['E', 'D', 'C', 'B']]
+        );
+        rope.move_crates(2, 2, 1, false);
+        assert_eq!(
+            rope.to_stacks(),
+            vec![vec!['A'], vec!['C', 'B'], vec!['E', 'D']]
+        );
+        rope.move_crates(0, 2, 1, true);
+        assert_eq!((rope.num_stacks(), rope.height(1)), (3, 2));
+    }
+
+    #[test]
+    fn test_same_as_vec() {
+        for seed in 0..20 {
+            let generator = Generator {
+                stacks: 4,
+                max_height: 30,
+                moves: 300,
+                seed,
+            };
+            let input = generator.generate().unwrap();
+            assert_eq!(
+                move_crates_rope(&input, true).unwrap(),
+                move_crates_vec(&input, &CrateMover9000).unwrap()
+            );
+            assert_eq!(
+                move_crates_rope(&input, false).unwrap(),
+                move_crates_vec(&input, &CrateMover9001).unwrap()
+            );
+        }
+    }
+}
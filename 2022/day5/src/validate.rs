@@ -0,0 +1,194 @@
+use std::{error, fmt};
+
+use crate::{Move, Stacks};
+
+/// A move that cannot be carried out on the stacks it is applied to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// The move names a stack that does not exist.
+    NoSuchStack {
+        index: usize,
+        line: Option<usize>,
+        stack: usize,
+        stacks: usize,
+    },
+    /// The origin stack holds fewer crates than the move takes.
+    NotEnoughCrates {
+        index: usize,
+        line: Option<usize>,
+        stack: usize,
+        wanted: usize,
+        available: usize,
+    },
+}
+
+impl MoveError {
+    /// Position of the move in the list of moves, starting at 0.
+    pub fn index(&self) -> usize {
+        match self {
+            MoveError::NoSuchStack { index, .. } | MoveError::NotEnoughCrates { index, .. } => {
+                *index
+            }
+        }
+    }
+
+    /// Line of the input the move was read from.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            MoveError::NoSuchStack { line, .. } | MoveError::NotEnoughCrates { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {}", self.index() + 1)?;
+        if let Some(line) = self.line() {
+            write!(f, " (line {})", line)?;
+        }
+        match self {
+            MoveError::NoSuchStack { stack, stacks, .. } => {
+                write!(f, ": there is no stack {}, only {}", stack, stacks)
+            }
+            MoveError::NotEnoughCrates {
+                stack,
+                wanted,
+                available,
+                ..
+            } => write!(
+                f,
+                ": stack {} holds {} crates, cannot move {}",
+                stack, available, wanted
+            ),
+        }
+    }
+}
+
+impl error::Error for MoveError {}
+
+/// What to do with moves that cannot be carried out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// Stop at the first invalid move.
+    #[default]
+    Strict,
+    /// Skip invalid moves.
+    Lenient,
+}
+
+/// Checks that the move at `index` can be carried out on `stacks`.
+pub fn check_move(stacks: &Stacks, mov: &Move, index: usize) -> Result<(), MoveError> {
+    check_heights(stacks.len(), |idx| stacks[idx].len(), mov, index)
+}
+
+/// Checks that the move at `index` can be carried out on `num_stacks`
+/// stacks, stack `idx` holding `height(idx)` crates.
+pub(crate) fn check_heights(
+    num_stacks: usize,
+    height: impl Fn(usize) -> usize,
+    mov: &Move,
+    index: usize,
+) -> Result<(), MoveError> {
+    for stack in [mov.from, mov.to] {
+        if stack == 0 || stack > num_stacks {
+            return Err(MoveError::NoSuchStack {
+                index,
+                line: mov.line,
+                stack,
+                stacks: num_stacks,
+            });
+        }
+    }
+    let available = height(mov.from - 1);
+    if available < mov.num {
+        return Err(MoveError::NotEnoughCrates {
+            index,
+            line: mov.line,
+            stack: mov.from,
+            wanted: mov.num,
+            available,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{move_crates, read_input, run_moves, CrateMover9000, MoveError, Validation};
+
+    const INPUT: &str = "\
+[A]
+[B] [C]
+ 1   2
+
+move 1 from 2 to 1
+move 3 from 1 to 2
+move 1 from 3 to 1
+move 2 from 1 to 2
+";
+
+    #[test]
+    fn test_strict() {
+        let input = read_input(INPUT).unwrap();
+        let err = move_crates(&input, &CrateMover9000).unwrap_err();
+        assert_eq!(
+            err,
+            MoveError::NoSuchStack {
+                index: 2,
+                line: Some(7),
+                stack: 3,
+                stacks: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "move 3 (line 7): there is no stack 3, only 2"
+        );
+    }
+
+    #[test]
+    fn test_huge_moves() {
+        let line = format!("move {} from 1 to 1\n", usize::MAX);
+        let input = read_input(&format!("[A]\n 1 \n\n{}{}", line, line)).unwrap();
+        assert_eq!(
+            move_crates(&input, &CrateMover9000).unwrap_err(),
+            MoveError::NotEnoughCrates {
+                index: 0,
+                line: Some(4),
+                stack: 1,
+                wanted: usize::MAX,
+                available: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = read_input(INPUT).unwrap();
+        let executed =
+            run_moves(&input, &CrateMover9000, Validation::Lenient, |_, _| Ok(())).unwrap();
+        assert_eq!(executed.stacks, vec![vec![], vec!['C', 'A', 'B']]);
+        let skipped = executed
+            .skipped
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            skipped,
+            vec![
+                "move 3 (line 7): there is no stack 3, only 2",
+                "move 4 (line 8): stack 1 holds 0 crates, cannot move 2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_example_is_valid() {
+        let input = read_input(&read_to_string("input/example").unwrap()).unwrap();
+        let executed =
+            run_moves(&input, &CrateMover9000, Validation::Strict, |_, _| Ok(())).unwrap();
+        assert!(executed.skipped.is_empty());
+    }
+}
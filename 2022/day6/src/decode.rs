@@ -0,0 +1,110 @@
+use std::{fmt, ops::Range};
+
+use common::Part;
+
+use crate::{markers, part_window};
+
+/// A marker and the data following it up to the next marker, positions
+/// counted in characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub marker: Range<usize>,
+    pub payload: Range<usize>,
+    pub data: String,
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "marker {}..{}, payload {}..{}: {}",
+            self.marker.start, self.marker.end, self.payload.start, self.payload.end, self.data
+        )
+    }
+}
+
+/// Splits `datastream` into frames, each starting with a marker of `window`
+/// distinct characters. The search for the next marker starts over after
+/// each marker, so markers never overlap, and a payload runs until the next
+/// marker starts. Data before the first marker belongs to no frame.
+pub fn split_frames(datastream: &str, window: usize) -> Vec<Frame> {
+    let chars = datastream.chars().collect::<Vec<char>>();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut start = 0;
+    while let Some(end) = markers(&chars[start..], window).next() {
+        let marker = start + end - window..start + end;
+        if let Some(last) = frames.last_mut() {
+            last.payload.end = marker.start;
+        }
+        start = marker.end;
+        frames.push(Frame {
+            marker,
+            payload: start..chars.len(),
+            data: String::new(),
+        });
+    }
+    for frame in frames.iter_mut() {
+        frame.data = chars[frame.payload.clone()].iter().collect();
+    }
+    frames
+}
+
+/// The datastream split at start-of-packet markers and, separately, at
+/// start-of-message markers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub packets: Vec<Frame>,
+    pub messages: Vec<Frame>,
+}
+
+pub fn decode(datastream: &str) -> Decoded {
+    Decoded {
+        packets: split_frames(datastream, part_window(Part::Part1)),
+        messages: split_frames(datastream, part_window(Part::Part2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{decode, read_datastream, split_frames, Frame};
+
+    #[test]
+    fn test_split_frames() {
+        let frames = split_frames("aaabcdxxxxwxyzyy", 4);
+        assert_eq!(
+            frames,
+            vec![
+                Frame {
+                    marker: 2..6,
+                    payload: 6..10,
+                    data: "xxxx".to_string()
+                },
+                Frame {
+                    marker: 10..14,
+                    payload: 14..16,
+                    data: "yy".to_string()
+                },
+            ]
+        );
+        assert_eq!(frames[0].to_string(), "marker 2..6, payload 6..10: xxxx");
+        assert!(split_frames("abab", 3).is_empty());
+    }
+
+    #[test]
+    fn test_decode() {
+        let input = read_datastream(&read_to_string("input/example").unwrap()).unwrap();
+        let decoded = decode(&input);
+        assert_eq!(decoded.packets[0].marker.end, 7);
+        assert_eq!(decoded.packets.len(), 6);
+        assert_eq!(
+            decoded.messages,
+            vec![Frame {
+                marker: 5..19,
+                payload: 19..30,
+                data: "jfqwrcgsmlb".to_string()
+            }]
+        );
+    }
+}
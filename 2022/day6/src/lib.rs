@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+
+use common::{Answer, Part, Solution};
+
+mod decode;
+mod search;
+mod stream;
+
+pub use decode::{decode, split_frames, Decoded, Frame};
+pub use search::{markers, MarkerDetector, Markers};
+pub use stream::{find_marker, find_markers, scan_markers};
+
+/// The datastream, the first line of the input.
+pub fn read_datastream(input: &str) -> Result<String> {
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| anyhow!("Empty datastream"))?;
+    Ok(line.to_string())
+}
+
+/// Number of distinct characters making a marker in the puzzle part.
+pub fn part_window(part: Part) -> usize {
+    match part {
+        Part::Part1 => 4,
+        Part::Part2 => 14,
+    }
+}
+
+fn get_start_pos(input: &str, part: Part) -> Option<usize> {
+    markers(input.chars(), part_window(part)).next()
+}
+
+fn find_start_pos(input: &str, part: Part) -> Result<Answer> {
+    let start_pos =
+        get_start_pos(input, part).ok_or_else(|| anyhow!("Failed to find start position"))?;
+    Ok(start_pos.into())
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String> {
+        read_datastream(input)
+    }
+
+    fn part1(input: &String) -> Result<Answer> {
+        find_start_pos(input, Part::Part1)
+    }
+
+    fn part2(input: &String) -> Result<Answer> {
+        find_start_pos(input, Part::Part2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{get_start_pos, read_datastream};
+    use common::Part;
+
+    #[test]
+    fn test_part1() {
+        let input = read_datastream(&read_to_string("input/example").unwrap()).unwrap();
+        let start_pos = get_start_pos(&input, Part::Part1).unwrap();
+        assert_eq!(start_pos, 7)
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_datastream(&read_to_string("input/example").unwrap()).unwrap();
+        let start_pos = get_start_pos(&input, Part::Part2).unwrap();
+        assert_eq!(start_pos, 19)
+    }
+}
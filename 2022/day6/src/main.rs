@@ -1,74 +1,105 @@
 use std::{
-    collections::HashSet,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, Read},
 };
 
 use anyhow::{anyhow, Error, Result};
+use clap::Parser;
 
-use common::{get_filename, Part};
+use common::{run_entry, Entry, InputSource, Part, Solution};
+use day6::{find_markers, markers, part_window, read_datastream, scan_markers, split_frames, Day6};
 
-fn read_file(filename: &str) -> Result<String> {
-    let file = File::open(filename)?;
-    let mut buf = String::new();
-    BufReader::new(&file).read_line(&mut buf)?;
-    Ok(buf)
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Day6Args {
+    /// Number of distinct characters making a marker, instead of those of
+    /// the selected parts
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    window: Option<u16>,
+    /// Print every marker rather than the first one
+    #[arg(long, conflicts_with = "decode")]
+    all: bool,
+    /// Split the datastream into frames, each a marker and the data up to
+    /// the next marker: packets and messages for the selected parts
+    #[arg(long, conflicts_with = "stream")]
+    decode: bool,
+    /// Scan the datastream as it is read instead of loading it whole, for
+    /// captures too large to fit in memory. Counts bytes rather than
+    /// characters
+    #[arg(long)]
+    stream: bool,
+    #[command(flatten)]
+    run: common::Args,
 }
 
-fn get_start_pos(input: &str, part: Part) -> Option<usize> {
-    let num_consecutive_letters = match part {
-        Part::Part1 => 4,
-        Part::Part2 => 14,
-    };
-    input
-        .chars()
-        .collect::<Vec<char>>()
-        .as_slice()
-        .windows(num_consecutive_letters)
-        .enumerate()
-        .fold(None, |res: Option::<usize>, (idx, chars)| {
-            if res.is_none() {
-                if chars.iter().collect::<HashSet<&char>>().len() == num_consecutive_letters {
-                    // println!("yes: {:?}", chars);
-                    Some(num_consecutive_letters + idx)
-                } else {
-                    // println!("not: {:?}", chars);
-                    None
-                }
-            } else {
-                res
-            }
-        })
+fn open(source: InputSource) -> Result<Box<dyn Read>> {
+    Ok(match source {
+        InputSource::File(filename) => Box::new(File::open(filename)?),
+        InputSource::Stdin => Box::new(io::stdin().lock()),
+        source => Box::new(io::Cursor::new(source.read(Day6::DAY)?)),
+    })
 }
 
 fn main() -> Result<(), Error> {
-    let filename = get_filename();
-    let input = read_file(&filename)?;
-    let start_pos_part1 =
-        get_start_pos(&input, Part::Part1).ok_or_else(|| anyhow!("Failed to find start position"))?;
-    println!("part 1: {}", start_pos_part1);
-    let start_pos_part2 =
-        get_start_pos(&input, Part::Part2).ok_or_else(|| anyhow!("Failed to find start position"))?;
-    println!("part 2: {}", start_pos_part2);
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{get_start_pos, read_file};
-    use common::Part;
-
-    #[test]
-    fn test_part1() {
-        let input = read_file("input/example").unwrap();
-        let start_pos = get_start_pos(&input, Part::Part1).unwrap();
-        assert_eq!(start_pos, 7)
+    let args = Day6Args::parse();
+    if args.window.is_none() && !args.all && !args.stream && !args.decode {
+        return run_entry(Entry::of::<Day6>(), &args.run);
     }
-
-    #[test]
-    fn test_part2() {
-        let input = read_file("input/example").unwrap();
-        let start_pos = get_start_pos(&input, Part::Part2).unwrap();
-        assert_eq!(start_pos, 19)
+    let (labels, windows): (Vec<String>, Vec<usize>) = match args.window {
+        Some(window) => {
+            let label = if args.decode {
+                "frame".to_string()
+            } else {
+                format!("window {}", window)
+            };
+            (vec![label], vec![window as usize])
+        }
+        None => args
+            .run
+            .parts()
+            .parts()
+            .into_iter()
+            .map(|part| {
+                let label = match (args.decode, part) {
+                    (true, Part::Part1) => "packet".to_string(),
+                    (true, Part::Part2) => "message".to_string(),
+                    (false, _) => format!("part {}", part.number()),
+                };
+                (label, part_window(part))
+            })
+            .unzip(),
+    };
+    let not_found = |label: &String| anyhow!("{}: failed to find start position", label);
+    if args.stream {
+        let reader = open(args.run.source())?;
+        if args.all {
+            scan_markers(reader, &windows, |idx, marker| {
+                println!("{}: {}", labels[idx], marker)
+            })?;
+        } else {
+            for (label, marker) in labels.iter().zip(find_markers(reader, &windows)?) {
+                println!("{}: {}", label, marker.ok_or_else(|| not_found(label))?);
+            }
+        }
+        return Ok(());
     }
+    let datastream = read_datastream(&args.run.source().read(Day6::DAY)?)?;
+    if args.decode {
+        for (label, window) in labels.iter().zip(windows) {
+            for (idx, frame) in split_frames(&datastream, window).iter().enumerate() {
+                println!("{} {}: {}", label, idx + 1, frame);
+            }
+        }
+        return Ok(());
+    }
+    for (label, window) in labels.iter().zip(windows) {
+        let mut found = markers(datastream.chars(), window).peekable();
+        if found.peek().is_none() {
+            return Err(not_found(label));
+        }
+        for marker in found.take(if args.all { usize::MAX } else { 1 }) {
+            println!("{}: {}", label, marker);
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Finds the runs of `window` distinct symbols in a datastream fed to it one
+/// symbol at a time, doing constant work per symbol. Symbols can be the
+/// chars of any alphabet, bytes or any other comparable tokens.
+#[derive(Clone, Debug)]
+pub struct MarkerDetector<T> {
+    window: usize,
+    /// The last `window` symbols, oldest first.
+    recent: VecDeque<T>,
+    /// How often each symbol appears in `recent`.
+    counts: HashMap<T, usize>,
+    /// Number of symbols appearing more than once in `recent`.
+    repeated: usize,
+    /// Number of symbols fed so far.
+    position: u64,
+    marker: Option<u64>,
+}
+
+impl<T: Clone + Eq + Hash> MarkerDetector<T> {
+    /// Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "the marker window must not be empty");
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window),
+            counts: HashMap::new(),
+            repeated: 0,
+            position: 0,
+            marker: None,
+        }
+    }
+
+    /// Position right after the first marker, once it has been fed.
+    pub fn marker(&self) -> Option<u64> {
+        self.marker
+    }
+
+    /// Feeds the next symbol of the datastream, returning the position after
+    /// it when it completes a marker. Markers overlap: every position after
+    /// a run of distinct symbols is returned.
+    pub fn push(&mut self, symbol: T) -> Option<u64> {
+        if self.recent.len() == self.window {
+            self.drop_oldest();
+        }
+        let count = self.counts.entry(symbol.clone()).or_insert(0);
+        *count += 1;
+        if *count == 2 {
+            self.repeated += 1;
+        }
+        self.recent.push_back(symbol);
+        self.position += 1;
+        if self.recent.len() < self.window || self.repeated > 0 {
+            return None;
+        }
+        self.marker.get_or_insert(self.position);
+        Some(self.position)
+    }
+
+    fn drop_oldest(&mut self) {
+        let Some(oldest) = self.recent.pop_front() else {
+            return;
+        };
+        if let Some(count) = self.counts.get_mut(&oldest) {
+            *count -= 1;
+            match *count {
+                0 => {
+                    self.counts.remove(&oldest);
+                }
+                1 => self.repeated -= 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Iterator over the positions right after every marker, see [`markers`].
+#[derive(Clone, Debug)]
+pub struct Markers<I: Iterator> {
+    symbols: I,
+    detector: MarkerDetector<I::Item>,
+}
+
+impl<I> Iterator for Markers<I>
+where
+    I: Iterator,
+    I::Item: Clone + Eq + Hash,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.symbols
+            .by_ref()
+            .find_map(|symbol| self.detector.push(symbol))
+            .map(|position| position as usize)
+    }
+}
+
+/// Finds every marker of `window` distinct symbols in `symbols` with a
+/// [`MarkerDetector`]. Panics if `window` is 0.
+pub fn markers<I>(symbols: I, window: usize) -> Markers<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Clone + Eq + Hash,
+{
+    Markers {
+        symbols: symbols.into_iter(),
+        detector: MarkerDetector::new(window),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use crate::{markers, read_datastream, MarkerDetector};
+
+    #[test]
+    fn test_all_markers() {
+        let input = read_datastream(&read_to_string("input/example").unwrap()).unwrap();
+        let found = markers(input.chars(), 4).take(5).collect::<Vec<usize>>();
+        assert_eq!(found, vec![7, 8, 9, 10, 11]);
+        assert_eq!(markers(input.chars(), 14).next(), Some(19));
+        assert_eq!(markers(input.chars(), 27).next(), None);
+    }
+
+    #[test]
+    fn test_alphabets() {
+        let found = markers("ΑΑβγΑδ".chars(), 3).collect::<Vec<usize>>();
+        assert_eq!(found, vec![4, 5, 6]);
+        let found = markers("A a A b".split(' '), 3).collect::<Vec<usize>>();
+        assert_eq!(found, vec![4]);
+        let found = markers([3, 1, 3, 3, 7, 1], 2).collect::<Vec<usize>>();
+        assert_eq!(found, vec![2, 3, 5, 6]);
+    }
+
+    #[test]
+    fn test_detector() {
+        let mut detector = MarkerDetector::new(3);
+        let found = "aabacd"
+            .bytes()
+            .map(|byte| detector.push(byte))
+            .collect::<Vec<Option<u64>>>();
+        assert_eq!(found, vec![None, None, None, None, Some(5), Some(6)]);
+        assert_eq!(detector.marker(), Some(5));
+    }
+}
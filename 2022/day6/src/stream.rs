@@ -0,0 +1,140 @@
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::MarkerDetector;
+
+/// Feeds `reader` to a detector per window, calling `on_marker` with the
+/// index of the window and the position after each marker, in stream order,
+/// for as long as it returns true. Like [`crate::read_datastream`], the
+/// datastream ends at the first line break.
+fn scan<R: Read>(
+    reader: R,
+    windows: &[usize],
+    mut on_marker: impl FnMut(usize, u64) -> bool,
+) -> io::Result<()> {
+    let mut detectors = windows
+        .iter()
+        .map(|&window| MarkerDetector::new(window))
+        .collect::<Vec<MarkerDetector<u8>>>();
+    let mut reader = BufReader::new(reader);
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let len = buf.len();
+        for &byte in buf {
+            if byte == b'\n' || byte == b'\r' {
+                return Ok(());
+            }
+            for (idx, detector) in detectors.iter_mut().enumerate() {
+                if let Some(position) = detector.push(byte) {
+                    if !on_marker(idx, position) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        reader.consume(len);
+    }
+}
+
+/// Reads the datastream of `reader`, calling `on_marker` with the index of
+/// the window and the position after every marker of `windows`, in stream
+/// order.
+pub fn scan_markers<R: Read>(
+    reader: R,
+    windows: &[usize],
+    mut on_marker: impl FnMut(usize, u64),
+) -> io::Result<()> {
+    scan(reader, windows, |idx, position| {
+        on_marker(idx, position);
+        true
+    })
+}
+
+/// Reads `reader` until a marker of every one of `windows` is found,
+/// returning the position after the first marker of each, if any. The
+/// datastream ends at the first line break.
+pub fn find_markers<R: Read>(reader: R, windows: &[usize]) -> io::Result<Vec<Option<u64>>> {
+    let mut markers = vec![None; windows.len()];
+    let mut missing = windows.len();
+    if missing == 0 {
+        return Ok(markers);
+    }
+    scan(reader, windows, |idx, position| {
+        if markers[idx].is_none() {
+            markers[idx] = Some(position);
+            missing -= 1;
+        }
+        missing > 0
+    })?;
+    Ok(markers)
+}
+
+/// Reads `reader` until the first marker of `window` distinct bytes.
+pub fn find_marker<R: Read>(reader: R, window: usize) -> io::Result<Option<u64>> {
+    Ok(find_markers(reader, &[window])?[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use crate::{find_marker, find_markers, scan_markers};
+
+    #[test]
+    fn test_find_markers() {
+        let examples = [
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (stream, packet, message) in examples {
+            let markers = find_markers(stream.as_bytes(), &[4, 14]).unwrap();
+            assert_eq!(markers, vec![Some(packet), Some(message)]);
+        }
+        assert_eq!(find_marker("abcabc".as_bytes(), 4).unwrap(), None);
+        assert_eq!(find_marker("".as_bytes(), 1).unwrap(), None);
+        assert_eq!(find_marker("abcabc\n".as_bytes(), 4).unwrap(), None);
+        assert_eq!(find_marker("abcabc\r\nd".as_bytes(), 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_scan_markers() {
+        let mut found = Vec::new();
+        scan_markers("abcabd\nefgh".as_bytes(), &[3, 4], |idx, position| {
+            found.push((idx, position))
+        })
+        .unwrap();
+        assert_eq!(found, vec![(0, 3), (0, 4), (0, 5), (0, 6), (1, 6)]);
+    }
+
+    /// Yields `len` bytes cycling through a repeating pattern, never holding
+    /// more than one buffer of it.
+    struct Pattern {
+        len: u64,
+        position: u64,
+    }
+
+    impl Read for Pattern {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min((self.len - self.position) as usize);
+            for byte in &mut buf[..n] {
+                *byte = b"abcab"[(self.position % 5) as usize];
+                self.position += 1;
+            }
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_long_stream() {
+        let stream = Pattern {
+            len: 1 << 20,
+            position: 0,
+        }
+        .chain("xyz".as_bytes());
+        assert_eq!(find_marker(stream, 4).unwrap(), Some((1 << 20) + 2));
+    }
+}